@@ -0,0 +1,36 @@
+//! Compare the last frames of two recordings, e.g. before and after a change of the rendering:
+//!
+//! `cargo run --example diff --features capture,recording -- before.ssrc after.ssrc diff.png`
+//!
+//! Pixels which were switched on are green, pixels which were switched off are red.
+
+use std::{env, error::Error, process::ExitCode};
+
+use steelseries_screen::{capture, recording::read_recording};
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let [before, after, output] = args.as_slice() else {
+        eprintln!("Usage: diff <before.ssrc> <after.ssrc> <diff.png>");
+        return Ok(ExitCode::FAILURE);
+    };
+
+    let (_, before) = read_recording(before)?
+        .pop()
+        .ok_or("the first recording has no frames")?;
+    let (_, after) = read_recording(after)?
+        .pop()
+        .ok_or("the second recording has no frames")?;
+    if before.lcd_type() != after.lcd_type() {
+        return Err(format!(
+            "cannot compare a {:?} recording with a {:?} recording",
+            before.lcd_type(),
+            after.lcd_type()
+        )
+        .into());
+    }
+
+    capture::diff(&before, &after, 4).save(output)?;
+    println!("Wrote {output}");
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,147 @@
+use std::time::{Duration, Instant};
+
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        iso_8859_1::{FONT_4X6, FONT_5X8, FONT_6X10},
+    },
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+use steelseries_screen::{
+    Error, GameSenseAPI, SteelSeriesDisplay,
+    text::{FIT_FONTS, FitText, RichText},
+    widgets::{Guides, HeatMap, Odometer, Ring, StepShape, Steps},
+};
+
+// a demo draws the given frame (10 per second) into the area below the caption
+type Demo = fn(&mut SteelSeriesDisplay, Rectangle, u32);
+
+const DEMOS: &[(&str, Demo)] = &[
+    ("Ring", ring),
+    ("Steps", steps),
+    ("Odometer", odometer),
+    ("Dithering", dithering),
+    ("Fonts", fonts),
+    ("Fit text", fit_text),
+    ("Rich text", rich_text),
+    ("Guides", guides),
+];
+
+fn main() -> Result<(), Error> {
+    // with `--features mock`, a local stand-in for SteelSeries Engine is used instead of GG
+    #[cfg(feature = "mock")]
+    let engine = steelseries_screen::mock::MockEngine::start()?;
+    #[cfg(feature = "mock")]
+    let mut api = GameSenseAPI::try_with_config("GALLERY".to_string(), engine.config())?;
+    #[cfg(not(feature = "mock"))]
+    let mut api = GameSenseAPI::try_new("GALLERY".to_string())?;
+    api.game_description("Showcase of the built-in widgets".to_string());
+    api.start()?;
+
+    // only run through the demos quickly without a real device
+    let frames = if cfg!(feature = "mock") { 2 } else { 40 };
+    let caption = MonoTextStyle::new(&FONT_4X6, BinaryColor::On);
+    for (name, demo) in DEMOS {
+        println!("{name}");
+        for frame in 0..frames {
+            let Some(display) = api.display_apex_mut() else {
+                break;
+            };
+            let _ = display.clear(BinaryColor::Off);
+            let _ = Text::with_baseline(name, Point::zero(), caption, Baseline::Top).draw(display);
+            let area = Rectangle::new(Point::new(0, 7), Size::new(128, 33));
+            demo(display, area, frame);
+            api.update_displays()?;
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+    api.shutdown()
+}
+
+fn ring(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    let progress = (frame % 40) as f32 / 39.0;
+    let label = format!("{}%", (progress * 100.0) as u32);
+    let style = MonoTextStyle::new(&FONT_5X8, BinaryColor::On);
+    let _ = Ring::new(area.center(), 30, progress)
+        .label(&label, style)
+        .draw(display);
+}
+
+fn steps(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    let style = MonoTextStyle::new(&FONT_5X8, BinaryColor::On);
+    let current = frame / 8 % 5;
+    let top_left = area.top_left + Point::new(4, 2);
+    let _ = Steps::new(top_left, 120, 5, current).draw(display);
+    let _ = Steps::new(top_left + Point::new(0, 10), 120, 5, current)
+        .shape(StepShape::Segments)
+        .size(4)
+        .label("Installing", style)
+        .draw(display);
+}
+
+fn odometer(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+    let start = Instant::now();
+    let mut odometer =
+        Odometer::new(0, area.top_left + Point::new(4, 20), style).duration(Duration::from_secs(3));
+    odometer.set_time(start);
+    odometer.set(123_456);
+    odometer.set_time(start + Duration::from_millis(u64::from(frame) * 100));
+    let _ = odometer.draw(display);
+}
+
+fn dithering(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    // a gradient moving to the right
+    let values: Vec<f32> = (0..2 * 30)
+        .map(|index| ((index % 30 + frame) % 30) as f32 / 29.0)
+        .collect();
+    let _ = HeatMap::new(&values, 30, area.top_left + Point::new(4, 4))
+        .cell_size(Size::new(4, 12))
+        .draw(display);
+}
+
+fn fonts(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    let font = FIT_FONTS[(frame / 8) as usize % FIT_FONTS.len()];
+    let style = MonoTextStyle::new(font, BinaryColor::On);
+    let name = format!(
+        "{}x{} Aa",
+        font.character_size.width, font.character_size.height
+    );
+    let _ = Text::with_baseline(&name, area.top_left, style, Baseline::Top).draw(display);
+}
+
+fn fit_text(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    // the text grows, the font shrinks to keep it within the area
+    let text = &"Shrinks to fit the area"[..(frame as usize % 23) + 1];
+    let _ = FitText::new(text, area, BinaryColor::On).draw(display);
+}
+
+fn rich_text(display: &mut SteelSeriesDisplay, area: Rectangle, _frame: u32) {
+    let small = MonoTextStyle::new(&FONT_5X8, BinaryColor::On);
+    let large = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+    let _ = RichText::new(area.top_left)
+        .text("Mixed ", small)
+        .inverted("fonts", large)
+        .text(" with wrapping at word boundaries", small)
+        .wrap(area.size.width)
+        .draw(display);
+}
+
+fn guides(display: &mut SteelSeriesDisplay, area: Rectangle, frame: u32) {
+    let style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+    let text = Text::with_baseline(
+        "Layout",
+        area.top_left + Point::new(8, 8),
+        style,
+        Baseline::Top,
+    );
+    let _ = text.draw(display);
+    let _ = Guides::new(area)
+        .grid(if frame % 20 < 10 { 8 } else { 4 })
+        .margin(2)
+        .bounds_of(&text)
+        .draw(display);
+}
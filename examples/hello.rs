@@ -1,35 +1,51 @@
-use std::time::Duration;
-
 use embedded_graphics::{
     mono_font::{MonoTextStyle, iso_8859_10::FONT_6X10},
     pixelcolor::BinaryColor,
     prelude::*,
     text::Text,
 };
-use steelseries_screen::GameSenseAPI;
+use steelseries_screen::{Error, GameSenseAPI, text::FontMetrics};
 
-fn main() {
-    let mut api = GameSenseAPI::new("HELLO_WORLD".to_string());
+fn main() -> Result<(), Error> {
+    // with `--features mock`, a local stand-in for SteelSeries Engine is used instead of GG
+    #[cfg(feature = "mock")]
+    let engine = steelseries_screen::mock::MockEngine::start()?;
+    #[cfg(feature = "mock")]
+    let mut api = GameSenseAPI::try_with_config("HELLO_WORLD".to_string(), engine.config())?;
+    #[cfg(not(feature = "mock"))]
+    let mut api = GameSenseAPI::try_new("HELLO_WORLD".to_string())?;
 
     // optional: set developer name and game-description
     api.developer("Max".to_string());
     api.game_description("This shows hello world!".to_string());
 
+    // draw before binding: the event is bound with the current content, so there is no blank frame
+    let text_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+    // place the baseline so that the text starts at the very top of the display
+    let baseline = FontMetrics::of(&text_style).baseline_for_top(0);
+    if let Some(display) = api.display_apex_mut() {
+        let _ = Text::new("Hello World!", Point::new(0, baseline), text_style).draw(display);
+    }
+
     // the GameSense API expects the 'game' (or application) to register before it sends data
-    api.register();
+    api.register()?;
     // after registration we also need to bind the event we're going to send
-    api.bind_event();
+    api.bind_event()?;
     // send a heartbeat every 10 seconds to prevent the display from being reset if no data is sent
     api.register_heartbeat();
 
-    let text_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
-    let _ = Text::new("Hello World!", Point::new(0, 6), text_style).draw(api.display_apex_mut());
-
-    // call update_displays to update the devices
+    // call update_displays to update the devices after drawing something new
     // note that in this example we only send data for a display of type apex (128x40).
-    api.update_displays();
+    api.update_displays()?;
 
     // show hello world for 60 seconds
-    std::thread::sleep(Duration::from_secs(60));
+    #[cfg(not(feature = "mock"))]
+    std::thread::sleep(std::time::Duration::from_secs(60));
     api.unregister_heartbeat();
+
+    #[cfg(feature = "mock")]
+    for request in engine.requests() {
+        println!("/{} {}", request.endpoint, request.body);
+    }
+    Ok(())
 }
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+use crate::{
+    display::{SteelSeriesDisplay, SteelSeriesLCDType},
+    frame_source::FrameSource,
+    widgets::dither,
+};
+
+/// How a frame designed for one display size is fitted onto another one
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Adapt {
+    /// Copy the pixels as they are, cutting off whatever doesn't fit
+    #[default]
+    Crop,
+    /// Scale to the target size, every pixel takes the nearest source pixel
+    Nearest,
+    /// Scale to the target size, every pixel covers an area of the source and is dithered by
+    /// the share of lit pixels in it. Keeps thin lines and gray areas visible when shrinking.
+    Area,
+}
+
+impl Adapt {
+    /// Draw the content of `source` onto `target` using this strategy
+    pub fn apply(self, source: &SteelSeriesDisplay, target: &mut SteelSeriesDisplay) {
+        let from = source.size();
+        let to = target.size();
+        let pixels = (0..to.height).flat_map(|y| {
+            (0..to.width).map(move |x| {
+                let on = match self {
+                    Adapt::Crop => lit(source, x, y),
+                    Adapt::Nearest => lit(
+                        source,
+                        scaled(x, from.width, to.width),
+                        scaled(y, from.height, to.height),
+                    ),
+                    Adapt::Area => {
+                        let columns = covered(x, from.width, to.width);
+                        let rows = covered(y, from.height, to.height);
+                        let total = columns.len() * rows.len();
+                        let on = rows
+                            .flat_map(|row| {
+                                columns
+                                    .clone()
+                                    .filter(move |&column| lit(source, column, row))
+                            })
+                            .count();
+                        #[allow(clippy::cast_precision_loss)]
+                        dither(on as f32 / total as f32, x, y)
+                    }
+                };
+                Pixel(Point::new(x.cast_signed(), y.cast_signed()), on.into())
+            })
+        });
+        let _ = target.draw_iter(pixels);
+    }
+}
+
+/// A frame designed for one display size, adapted to the size of every display it is rendered on
+///
+/// ```ignore
+/// let mut design = SteelSeriesDisplay::new(SteelSeriesLCDType::GameDAC);
+/// // draw the design at 128x52 ...
+/// api.splash(Adapted::new(design, Adapt::Nearest).on(SteelSeriesLCDType::Rival7x0, Adapt::Area));
+/// ```
+pub struct Adapted {
+    design: SteelSeriesDisplay,
+    strategy: Adapt,
+    strategies: HashMap<SteelSeriesLCDType, Adapt>,
+}
+
+impl Adapted {
+    /// Adapt `design` to every display using `strategy`
+    #[must_use]
+    pub fn new(design: SteelSeriesDisplay, strategy: Adapt) -> Adapted {
+        Adapted {
+            design,
+            strategy,
+            strategies: HashMap::new(),
+        }
+    }
+
+    /// Use another strategy for displays of the given type
+    #[must_use]
+    pub fn on(mut self, lcd_type: SteelSeriesLCDType, strategy: Adapt) -> Self {
+        self.strategies.insert(lcd_type, strategy);
+        self
+    }
+
+    /// The design, e.g. to draw the next frame
+    pub fn design_mut(&mut self) -> &mut SteelSeriesDisplay {
+        &mut self.design
+    }
+}
+
+/// Displays of the same type as the design get an exact copy.
+impl FrameSource for Adapted {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        if display.lcd_type() == self.design.lcd_type() {
+            display
+                .framebuffer
+                .copy_from_slice(&self.design.framebuffer);
+            return;
+        }
+        let strategy = self
+            .strategies
+            .get(&display.lcd_type())
+            .copied()
+            .unwrap_or(self.strategy);
+        strategy.apply(&self.design, display);
+    }
+}
+
+// Helper which reads a source pixel, everything outside of the display is off
+fn lit(display: &SteelSeriesDisplay, x: u32, y: u32) -> bool {
+    display
+        .pixel(Point::new(x.cast_signed(), y.cast_signed()))
+        .is_some_and(BinaryColor::is_on)
+}
+
+// Helper which maps a target coordinate to the source coordinate at the center of its pixel
+fn scaled(position: u32, from: u32, to: u32) -> u32 {
+    (2 * position + 1) * from / (2 * to.max(1))
+}
+
+// Helper which returns the source coordinates covered by a target coordinate (at least one)
+fn covered(position: u32, from: u32, to: u32) -> std::ops::Range<u32> {
+    let to = to.max(1);
+    let start = position * from / to;
+    let end = ((position + 1) * from).div_ceil(to).max(start + 1);
+    start..end
+}
@@ -0,0 +1,141 @@
+//! Alert levels for numeric data sources
+//!
+//! An [`AlertRule`] maps a stream of values (CPU temperature, free disk space, ping, ...) to an
+//! [`AlertLevel`]. Hysteresis and a hold time keep the level from flickering when a value hovers
+//! around a threshold, so the level can drive styling, notifications or lighting directly.
+//!
+//! ```ignore
+//! let mut temperature = AlertRule::above(70.0, 85.0)
+//!     .hysteresis(3.0)
+//!     .hold(Duration::from_secs(5));
+//! if let Some(level) = temperature.update(sensor.read()) {
+//!     println!("CPU temperature is now {level:?}");
+//! }
+//! ```
+
+use std::time::{Duration, Instant};
+
+/// The state of a monitored value, ordered by severity
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug, Default)]
+pub enum AlertLevel {
+    /// Everything is fine
+    #[default]
+    Ok,
+    /// The warning threshold is exceeded
+    Warn,
+    /// The critical threshold is exceeded
+    Critical,
+}
+
+/// Thresholds with hysteresis and hold time which turn values into [`AlertLevel`]s
+pub struct AlertRule {
+    // thresholds and values are negated for rules where low values are bad
+    sign: f64,
+    warn: f64,
+    critical: f64,
+    hysteresis: f64,
+    hold: Duration,
+    level: AlertLevel,
+    pending: Option<(AlertLevel, Instant)>,
+}
+
+impl AlertRule {
+    fn new(sign: f64, warn: f64, critical: f64) -> AlertRule {
+        AlertRule {
+            sign,
+            warn: sign * warn,
+            critical: sign * critical,
+            hysteresis: 0.0,
+            hold: Duration::ZERO,
+            level: AlertLevel::Ok,
+            pending: None,
+        }
+    }
+
+    /// A rule for values which are bad when high (e.g. temperatures)
+    /// # Arguments
+    ///
+    /// * `warn` - Values at or above this are [`AlertLevel::Warn`]
+    /// * `critical` - Values at or above this are [`AlertLevel::Critical`]
+    ///
+    #[must_use]
+    pub fn above(warn: f64, critical: f64) -> AlertRule {
+        AlertRule::new(1.0, warn, critical)
+    }
+
+    /// A rule for values which are bad when low (e.g. free disk space)
+    /// # Arguments
+    ///
+    /// * `warn` - Values at or below this are [`AlertLevel::Warn`]
+    /// * `critical` - Values at or below this are [`AlertLevel::Critical`]
+    ///
+    #[must_use]
+    pub fn below(warn: f64, critical: f64) -> AlertRule {
+        AlertRule::new(-1.0, warn, critical)
+    }
+
+    /// How far a value has to move back past a threshold before the level drops (defaults to 0)
+    #[must_use]
+    pub fn hysteresis(mut self, hysteresis: f64) -> Self {
+        self.hysteresis = hysteresis.abs();
+        self
+    }
+
+    /// How long a new level has to persist before it is adopted (defaults to 0)
+    #[must_use]
+    pub fn hold(mut self, hold: Duration) -> Self {
+        self.hold = hold;
+        self
+    }
+
+    /// The current level
+    #[must_use]
+    pub fn level(&self) -> AlertLevel {
+        self.level
+    }
+
+    /// Feed a new value, returns the new level if it changed
+    pub fn update(&mut self, value: f64) -> Option<AlertLevel> {
+        self.update_at(value, Instant::now())
+    }
+
+    /// Feed a new value measured at the given point in time (e.g. `clock.instant()` of a
+    /// [`Clock`](crate::Clock)), returns the new level if it changed
+    pub fn update_at(&mut self, value: f64, now: Instant) -> Option<AlertLevel> {
+        let target = self.target(self.sign * value);
+        if target == self.level {
+            self.pending = None;
+            return None;
+        }
+        let since = match self.pending {
+            Some((pending, since)) if pending == target => since,
+            _ => now,
+        };
+        if now.saturating_duration_since(since) < self.hold {
+            self.pending = Some((target, since));
+            return None;
+        }
+        self.pending = None;
+        self.level = target;
+        Some(target)
+    }
+
+    // the level for a value, levels at or below the current one are kept down to the hysteresis
+    fn target(&self, value: f64) -> AlertLevel {
+        let exceeds = |threshold: f64, level: AlertLevel| {
+            let margin = if self.level >= level {
+                self.hysteresis
+            } else {
+                0.0
+            };
+            value >= threshold - margin
+        };
+        if exceeds(self.critical, AlertLevel::Critical) {
+            AlertLevel::Critical
+        } else if exceeds(self.warn, AlertLevel::Warn) {
+            AlertLevel::Warn
+        } else {
+            AlertLevel::Ok
+        }
+    }
+}
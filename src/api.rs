@@ -7,17 +7,29 @@ use serde_json::{Map, json};
 use std::{
     collections::HashMap,
     fs::File,
-    io::{Error, Read},
-    sync::{
-        Arc,
-        atomic::{AtomicBool, Ordering},
-    },
-    time::Duration,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
-use crate::display::{SteelSeriesDisplay, SteelSeriesLCDType};
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
 
-const DEFAULT_EVENT: &str = "UPDATE";
+use crate::{
+    batch::{Batch, BatchEvent},
+    builder::GameSenseAPIBuilder,
+    config::Config,
+    display::{SteelSeriesDisplay, SteelSeriesLCDType},
+    error::Error,
+    events::EventBuilder,
+    frame_source::FrameSource,
+    handlers::{GoLisp, Handler, Repeat, ScreenHandler, text_line_key, validate},
+    heartbeat::{Heartbeat, HeartbeatHandle},
+    lighting::{BitmapHandler, KeyBitmap},
+    stats::{Activity, Counters, SessionStats},
+};
+
+pub(crate) const DEFAULT_EVENT: &str = "UPDATE";
 
 // Helper for parsing the json File which holds information on where to find the API endpoint
 #[derive(Deserialize, Debug)]
@@ -29,58 +41,196 @@ struct SteelSeriesAPIInfo {
     _gg_encrypted_address: Option<String>,
 }
 
-// Helper function which returns the address for the GameSense API
-// This address changes with every start of the SteelSeries Application
-fn get_api_addr() -> Result<String, Error> {
+// Helper which returns the platform specific location of coreProps.json
+fn default_core_props_path() -> PathBuf {
     #[cfg(target_os = "windows")]
-    let engine_path =
-        std::env::var("PROGRAMDATA").expect("Could not find env %PROGRAM DATA%") + "/SteelSeries";
+    let engine_path = std::env::var("PROGRAMDATA").unwrap_or_default() + "/SteelSeries";
     #[cfg(target_os = "macos")]
     let engine_path = "/Library/Application Support/";
-    let mut file = File::open(format!("{engine_path}/SteelSeries Engine 3/coreProps.json"))
-        .expect("Could not open SteelSeries Engine Information. Is SteelSeries Engine running?");
+    // SteelSeries GG is not available on other platforms, only a configured address works there
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let engine_path = ".";
+    PathBuf::from(format!("{engine_path}/SteelSeries Engine 3/coreProps.json"))
+}
+
+// Helper function which returns the address for the GameSense API
+// This address changes with every start of the SteelSeries Application
+fn get_api_addr(core_props_path: &Path) -> Result<String, Error> {
+    let not_found = || Error::EngineNotFound {
+        path: core_props_path.to_path_buf(),
+    };
+    let mut file = File::open(core_props_path).map_err(|_| not_found())?;
     let mut buff = String::new();
     file.read_to_string(&mut buff)?;
 
-    let data: SteelSeriesAPIInfo = serde_json::from_str(&buff)
-        .expect("Could not parse SteelSeries Engine endpoint. Is SteelSeries Engine running?");
+    let data: SteelSeriesAPIInfo = serde_json::from_str(&buff).map_err(|_| not_found())?;
     Ok(data.address)
 }
 
+// Helper which returns the configured address or discovers it
+pub(crate) fn resolve_address(config: &Config) -> Result<String, Error> {
+    match &config.address {
+        Some(address) => Ok(address.clone()),
+        None => get_api_addr(
+            &config
+                .core_props_path
+                .clone()
+                .unwrap_or_else(default_core_props_path),
+        ),
+    }
+}
+
+// Helper which re-reads coreProps.json after a connection was refused, returns the new address
+// if the Engine moved. Configured addresses are never changed.
+pub(crate) fn moved_address(config: &Config, current: &str) -> Option<String> {
+    if config.address.is_some() {
+        return None;
+    }
+    resolve_address(config)
+        .ok()
+        .filter(|address| address != current)
+}
+
 // Every game which wants to send data requires a game name and an event name
 #[derive(Serialize, Deserialize, Debug)]
-struct GameMetadata {
-    game: String,
+pub(crate) struct GameMetadata {
+    pub(crate) game: String,
     event: String,
     value_optional: bool,
-    game_display_name: Option<String>,
-    developer: Option<String>,
+    pub(crate) game_display_name: Option<String>,
+    pub(crate) developer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deinitialize_timer_length_ms: Option<u64>,
+}
+
+impl GameMetadata {
+    pub(crate) fn new(game_name: String, config: &Config) -> GameMetadata {
+        GameMetadata {
+            developer: config.developer.clone(),
+            event: DEFAULT_EVENT.to_string(),
+            game: game_name,
+            game_display_name: config.game_display_name.clone(),
+            value_optional: true,
+            // the Engine only accepts 1 to 60 seconds
+            deinitialize_timer_length_ms: config
+                .deinitialize_timer_ms
+                .map(|timer| timer.clamp(1_000, 60_000)),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
-struct BindGameEvent {
+pub(crate) struct BindGameEvent {
     game: String,
     value_optional: bool,
     handlers: serde_json::Value,
     event: String,
 }
 
+impl BindGameEvent {
+    pub(crate) fn new(game: &str, event: &str, handlers: &[&dyn Handler]) -> BindGameEvent {
+        BindGameEvent {
+            game: game.to_string(),
+            event: event.to_string(),
+            value_optional: true,
+            handlers: handlers.iter().map(|handler| handler.to_json()).collect(),
+        }
+    }
+
+    // The default event shows bitmaps on all displays, initialized with their current content
+    pub(crate) fn screens(
+        game: &str,
+        displays: &HashMap<SteelSeriesLCDType, SteelSeriesDisplay>,
+    ) -> BindGameEvent {
+        let handlers: Vec<ScreenHandler> = displays.values().map(ScreenHandler::bitmap).collect();
+        let handlers: Vec<&dyn Handler> = handlers
+            .iter()
+            .map(|handler| handler as &dyn Handler)
+            .collect();
+        BindGameEvent::new(game, DEFAULT_EVENT, &handlers)
+    }
+
+    // Add another handler to the event
+    pub(crate) fn with(mut self, handler: &dyn Handler) -> BindGameEvent {
+        if let Some(handlers) = self.handlers.as_array_mut() {
+            handlers.push(handler.to_json());
+        }
+        self
+    }
+
+    // Check all handlers against the schema of the GameSense SDK
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        let handlers = self.handlers.as_array().map_or(&[][..], Vec::as_slice);
+        for (index, handler) in handlers.iter().enumerate() {
+            validate(handler).map_err(|message| Error::InvalidHandler { index, message })?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
-struct GameEvent {
+pub(crate) struct GameEvent {
     game: String,
     event: String,
-    data: serde_json::Value,
+    pub(crate) data: serde_json::Value,
+}
+
+impl GameEvent {
+    pub(crate) fn new(game: &str, event: &str, data: serde_json::Value) -> GameEvent {
+        GameEvent {
+            game: game.to_string(),
+            event: event.to_string(),
+            data,
+        }
+    }
+
+    // An event carrying the content of all displays
+    pub(crate) fn frame(
+        game: &str,
+        event: &str,
+        displays: &HashMap<SteelSeriesLCDType, SteelSeriesDisplay>,
+    ) -> GameEvent {
+        let mut img_datas: Map<String, serde_json::Value> = Map::new();
+        for (lcd_type, display) in displays {
+            let dimensions = lcd_type.dimensions();
+            img_datas.insert(
+                format!("image-data-{}x{}", dimensions.width, dimensions.height),
+                display.framebuffer.as_slice().into(),
+            );
+        }
+        GameEvent::new(
+            game,
+            event,
+            json!({
+                "frame": img_datas
+            }),
+        )
+    }
 }
 
 pub struct GameSenseAPI {
     game_metadata: GameMetadata,
     client: Arc<reqwest::blocking::Client>,
-    address: String,
+    address: Mutex<String>,
     headers: Arc<HeaderMap<HeaderValue>>,
     displays: HashMap<SteelSeriesLCDType, SteelSeriesDisplay>,
-    send_heartbeat: Arc<AtomicBool>,
+    key_bitmap: Option<KeyBitmap>,
+    heartbeat: Option<Arc<Heartbeat>>,
+    pub(crate) splash: Option<Box<dyn FrameSource + Send + Sync>>,
+    pub(crate) goodbye: Option<Box<dyn FrameSource + Send + Sync>>,
+    config: Config,
+    counters: Arc<Counters>,
+    // event and body of the latest frame skipped while the Engine was slow
+    pending_frame: Arc<Mutex<Option<(String, String)>>>,
+    on_degradation: Option<Box<dyn Fn(bool) + Send + Sync>>,
 }
 
+// the API is moved into worker threads, so it has to stay `Send` and `Sync`
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<GameSenseAPI>();
+};
+
 impl GameSenseAPI {
     /// Create a new instance of the GameSense API
     /// An instance of the API will hold all displays for each type of device. Currently the GameSense API supports 4 types
@@ -97,17 +247,62 @@ impl GameSenseAPI {
     ///
     /// * `game_name` - A game name which will be shown in the SteelSeries Desktop Application. Allowed are upper-case A-Z, 0-9, hyphen, and underscore.
     ///
+    /// # Panics
+    ///
+    /// Panics if SteelSeries Engine can't be found, use [`GameSenseAPI::try_new`] to handle this.
+    #[must_use]
     pub fn new(game_name: String) -> GameSenseAPI {
-        let game_metadata = GameMetadata {
-            developer: None,
-            event: DEFAULT_EVENT.to_string(),
-            game: game_name,
-            game_display_name: None,
-            value_optional: true,
-        };
+        Self::with_config(game_name, Config::default())
+    }
+
+    /// Configure a new instance of the GameSense API step by step, see [`GameSenseAPIBuilder`]
+    ///
+    /// # Arguments
+    ///
+    /// * `game_name` - A game name which will be shown in the SteelSeries Desktop Application. Allowed are upper-case A-Z, 0-9, hyphen, and underscore.
+    ///
+    #[must_use]
+    pub fn builder(game_name: impl Into<String>) -> GameSenseAPIBuilder {
+        GameSenseAPIBuilder::new(game_name.into())
+    }
 
-        // create a hashmap with all display sizes currently known
-        let displays: HashMap<_, _> = SteelSeriesLCDType::all()
+    /// Like [`GameSenseAPI::new`], but returns an error instead of panicking. Useful for apps
+    /// which start before SteelSeries GG and want to retry later.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EngineNotFound`] if SteelSeries Engine can't be found.
+    pub fn try_new(game_name: String) -> Result<GameSenseAPI, Error> {
+        Self::try_with_config(game_name, Config::default())
+    }
+
+    /// Create a new instance of the GameSense API with custom settings, see [`Config`].
+    /// Only the displays listed in the config are managed; the `display_*_mut()` accessors of
+    /// other display types return `None`. `SSSCREEN_*` environment variables take precedence over the
+    /// given config.
+    ///
+    /// # Panics
+    ///
+    /// Panics if SteelSeries Engine can't be found, use [`GameSenseAPI::try_with_config`] to
+    /// handle this.
+    #[must_use]
+    pub fn with_config(game_name: String, config: Config) -> GameSenseAPI {
+        Self::try_with_config(game_name, config).expect("SteelSeries Engine not reachable!")
+    }
+
+    /// Like [`GameSenseAPI::with_config`], but returns an error instead of panicking.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EngineNotFound`] if SteelSeries Engine can't be found or [`Error::Http`]
+    /// if the HTTP client could not be created.
+    pub fn try_with_config(game_name: String, mut config: Config) -> Result<GameSenseAPI, Error> {
+        config.apply_env();
+        let game_metadata = GameMetadata::new(game_name, &config);
+
+        // create a hashmap with all configured display sizes
+        let displays: HashMap<_, _> = config
+            .displays
             .iter()
             .map(|lcd_type| (*lcd_type, SteelSeriesDisplay::new(*lcd_type)))
             .collect();
@@ -116,14 +311,25 @@ impl GameSenseAPI {
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         let headers = Arc::new(headers);
 
-        GameSenseAPI {
-            client: Arc::new(reqwest::blocking::Client::new()),
+        let client = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+
+        Ok(GameSenseAPI {
+            client: Arc::new(client),
             game_metadata,
-            address: get_api_addr().expect("SteelSeries Engine not reachable!"),
+            address: Mutex::new(resolve_address(&config)?),
             headers,
             displays,
-            send_heartbeat: Arc::new(AtomicBool::new(false)),
-        }
+            key_bitmap: None,
+            heartbeat: None,
+            splash: None,
+            goodbye: None,
+            config,
+            counters: Arc::new(Counters::new()),
+            pending_frame: Arc::new(Mutex::new(None)),
+            on_degradation: None,
+        })
     }
 
     /// Optionally set a developer name for this game. Will be shown in SteelSeries GG Client.
@@ -136,140 +342,818 @@ impl GameSenseAPI {
         self.game_metadata.game_display_name = Some(description);
     }
 
+    /// Optionally set a splash screen which is drawn onto every display by `start()`.
+    /// The source is rendered once per display type, so it can adapt to the display size.
+    pub fn splash<F: FrameSource + Send + Sync + 'static>(&mut self, splash: F) {
+        self.splash = Some(Box::new(splash));
+    }
+
+    /// Optionally set a frame which is drawn onto every display by `shutdown()`.
+    /// Without it, the displays are cleared on shutdown.
+    pub fn goodbye<F: FrameSource + Send + Sync + 'static>(&mut self, goodbye: F) {
+        self.goodbye = Some(Box::new(goodbye));
+    }
+
+    /// Register the game and bind the event in one go. If a splash screen is set, it is shown
+    /// right away as the event is bound with it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the registration or binding.
+    pub fn start(&mut self) -> Result<(), Error> {
+        self.register()?;
+        if let Some(splash) = &mut self.splash {
+            for display in self.displays.values_mut() {
+                splash.render(display);
+            }
+        }
+        self.bind_event()
+    }
+
+    /// Stop the heartbeat and show the goodbye frame (or a blank screen) on all displays.
+    /// Without a goodbye frame, the game is stopped right away so that the devices return to
+    /// their default screen instead of showing a frozen frame. A goodbye frame stays until the
+    /// Engine times out. The registration is kept, see [`GameSenseAPI::remove_game`].
+    /// The final frame is sent even while the Engine is slow, as no heartbeat is left to send it
+    /// later.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the final frame.
+    pub fn shutdown(&mut self) -> Result<(), Error> {
+        self.unregister_heartbeat();
+        for display in self.displays.values_mut() {
+            let _ = display.clear(BinaryColor::Off);
+            if let Some(goodbye) = &mut self.goodbye {
+                goodbye.render(display);
+            }
+        }
+        self.post_frame(DEFAULT_EVENT, &self.frame(DEFAULT_EVENT))?;
+        if self.goodbye.is_none() {
+            self.stop_game()?;
+        }
+        Ok(())
+    }
+
+    /// Tell the Engine that the game stopped, so the devices return to their default screen
+    /// immediately instead of waiting for the timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub fn stop_game(&self) -> Result<(), Error> {
+        self.post(
+            "stop_game",
+            &json!({
+                "game": self.game_metadata.game
+            }),
+        )
+    }
+
     /// Register our game to the GameSense API.
-    pub fn register(&self) {
-        let data = serde_json::to_string(&self.game_metadata)
-            .expect("Could not serialize JSON body for registration");
-        let res = self
-            .client
-            .post(format!("http://{}/game_metadata", self.address))
-            .body(data)
-            .headers((*self.headers).clone())
-            .send();
-        check_response(res.unwrap());
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the registration.
+    pub fn register(&self) -> Result<(), Error> {
+        self.post("game_metadata", &self.game_metadata)
+    }
+
+    /// Remove the game and all of its events from SteelSeries GG, e.g. on uninstall or when
+    /// cleaning up test registrations.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub fn remove_game(&self) -> Result<(), Error> {
+        self.post(
+            "remove_game",
+            &json!({
+                "game": self.game_metadata.game
+            }),
+        )
+    }
+
+    /// Remove a previously bound event, e.g. when the handler layout changed between versions
+    /// of the app.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub fn remove_game_event(&self, event: &str) -> Result<(), Error> {
+        self.post(
+            "remove_game_event",
+            &json!({
+                "game": self.game_metadata.game,
+                "event": event
+            }),
+        )
     }
 
     /// Bind the UPDATE event. This must be called AFTER the registration of the game.
-    pub fn bind_event(&self) {
-        let mut handler_datas: Vec<serde_json::Value> = vec![];
+    /// The handlers are initialized with the current content of the displays, so anything drawn
+    /// before binding (e.g. a splash image) shows up immediately instead of a blank screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the handlers.
+    pub fn bind_event(&self) -> Result<(), Error> {
+        let mut bind = BindGameEvent::screens(&self.game_metadata.game, &self.displays);
+        if self.key_bitmap.is_some() {
+            bind = bind.with(&BitmapHandler);
+        }
+        self.post("bind_game_event", &bind)
+    }
 
-        for lcd_type in self.displays.keys() {
-            let dimensions = lcd_type.dimensions();
-            let empty_data = vec![0; dimensions.width as usize * dimensions.height as usize / 8];
-            handler_datas.push(json!({
-                "zone": "one",
-                "device-type": format!("screened-{}x{}", dimensions.width, dimensions.height),
-                "mode": "screen",
-                "datas": [{
-                    "has-text": false,
-                    "image-data": empty_data
-                }]
-            }));
+    /// Bind an animation which the Engine plays on all displays in lockstep
+    ///
+    /// Every frame source is rendered once per display type, and all displays get the same
+    /// number of frames with the same `length`. As the handlers of all devices are bound to the
+    /// same event, a single event starts the animation on every device at once, so they don't
+    /// drift apart like frames sent one after the other. The displays of the API are not changed.
+    ///
+    /// Animations larger than [`Config::max_payload_bytes`] are split at frame boundaries into
+    /// several events: the first part is bound to `event`, the following ones to `event-2`,
+    /// `event-3` and so on. Every part plays once and only the last one is repeated, so send the
+    /// events one after the other, each once the previous part finished. Returns the bound events
+    /// in playback order together with the time their frames take.
+    ///
+    /// ```ignore
+    /// let mut frames: Vec<Box<dyn FrameSource>> = ...;
+    /// let mut frames: Vec<&mut dyn FrameSource> = frames.iter_mut().map(|frame| frame.as_mut()).collect();
+    /// let parts = api.bind_animation("INTRO", &mut frames, Duration::from_millis(100), Repeat::Times(3))?;
+    /// for (event, duration) in parts {
+    ///     api.send_event_data(&event, json!({}))?;
+    ///     std::thread::sleep(duration);
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::PayloadTooLarge`] if a single frame doesn't fit into the maximum payload
+    /// size, or an error if the Engine is not reachable or rejects the handlers.
+    pub fn bind_animation(
+        &self,
+        event: &str,
+        frames: &mut [&mut dyn FrameSource],
+        length: Duration,
+        repeat: Repeat,
+    ) -> Result<Vec<(String, Duration)>, Error> {
+        let mut lcd_types: Vec<SteelSeriesLCDType> = self.displays.keys().copied().collect();
+        lcd_types.sort_by_key(|lcd_type| lcd_type.dimensions().height);
+        let rendered: Vec<Vec<SteelSeriesDisplay>> = lcd_types
+            .into_iter()
+            .map(|lcd_type| {
+                frames
+                    .iter_mut()
+                    .map(|frame| {
+                        let mut display = SteelSeriesDisplay::new(lcd_type);
+                        frame.render(&mut display);
+                        display
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let count = frames.len();
+        let limit = self.config.max_payload_bytes;
+        let mut parts = vec![];
+        let mut start = 0;
+        while start < count || parts.is_empty() {
+            let name = match parts.len() {
+                0 => event.to_string(),
+                index => format!("{event}-{}", index + 1),
+            };
+            let bind = |end: usize| {
+                let repeat = (end == count).then_some(repeat);
+                self.animation_part(&name, &rendered, start..end, length, repeat)
+            };
+            // the longest part which fits, the size grows with every frame
+            let (mut fits, mut too_large) = (start, count + 1);
+            while too_large - fits > 1 {
+                let end = fits.midpoint(too_large);
+                if payload_size(&bind(end)) <= limit {
+                    fits = end;
+                } else {
+                    too_large = end;
+                }
+            }
+            if fits == start && start < count {
+                return Err(Error::PayloadTooLarge {
+                    size: payload_size(&bind(start + 1)),
+                    limit,
+                });
+            }
+            let frames = u32::try_from(fits - start).unwrap_or(u32::MAX);
+            parts.push((name.clone(), bind(fits), length.saturating_mul(frames)));
+            start = fits;
         }
-        let data = serde_json::to_string(&BindGameEvent {
-            game: self.game_metadata.game.clone(),
-            event: DEFAULT_EVENT.to_string(),
-            value_optional: true,
-            handlers: handler_datas.into(),
-        })
-        .expect("Could not serialize data for JSON bind event");
 
-        let res = self
-            .client
-            .post(format!("http://{}/bind_game_event", self.address))
-            .body(data)
-            .headers((*self.headers).clone())
-            .send();
-        check_response(res.unwrap());
+        let mut events = vec![];
+        for (name, bind, duration) in parts {
+            bind.validate()?;
+            self.post("bind_game_event", &bind)?;
+            events.push((name, duration));
+        }
+        Ok(events)
     }
 
-    /// Call this method to update the screens.
-    pub fn update_displays(&self) {
-        let mut img_datas: Map<String, serde_json::Value> = Map::new();
-        for (lcd_type, display) in &self.displays {
-            let dimensions = lcd_type.dimensions();
-            img_datas.insert(
-                format!("image-data-{}x{}", dimensions.width, dimensions.height),
-                display.framebuffer.as_slice().into(),
-            );
+    // Helper which binds the frames in `range` of every display type to `event`
+    fn animation_part(
+        &self,
+        event: &str,
+        rendered: &[Vec<SteelSeriesDisplay>],
+        range: std::ops::Range<usize>,
+        length: Duration,
+        repeat: Option<Repeat>,
+    ) -> BindGameEvent {
+        let handlers: Vec<ScreenHandler> = rendered
+            .iter()
+            .filter_map(|displays| {
+                let (first, rest) = displays[range.clone()].split_first()?;
+                let handler = rest.iter().fold(
+                    ScreenHandler::bitmap(first).length(length),
+                    |handler, display| handler.frame(display, length),
+                );
+                Some(match repeat {
+                    Some(repeat) => handler.repeat(repeat),
+                    None => handler,
+                })
+            })
+            .collect();
+        let handlers: Vec<&dyn Handler> = handlers
+            .iter()
+            .map(|handler| handler as &dyn Handler)
+            .collect();
+        BindGameEvent::new(&self.game_metadata.game, event, &handlers)
+    }
+
+    /// Register the metadata of a custom event, so that it shows up in SteelSeries GG with an
+    /// icon and a value range. Optional, binding an event registers it as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the event.
+    pub fn register_game_event(&self, event: &EventBuilder) -> Result<(), Error> {
+        self.post(
+            "register_game_event",
+            &event.to_json(&self.game_metadata.game),
+        )
+    }
+
+    /// Bind a custom event (e.g. `HEALTH`) with its own handlers. Event names are limited to
+    /// upper-case A-Z, 0-9, hyphen, and underscore. This must be called AFTER the registration of
+    /// the game.
+    ///
+    /// ```ignore
+    /// let apex = api.display_apex_mut().expect("the Apex display is managed");
+    /// let apex = ScreenHandler::bitmap(apex);
+    /// api.bind_game_event("SCREEN", &[&apex])?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidHandler`] if a handler does not match the schema of the GameSense
+    /// SDK, or an error if the Engine is not reachable or rejects the handlers.
+    pub fn bind_game_event(&self, event: &str, handlers: &[&dyn Handler]) -> Result<(), Error> {
+        let bind = BindGameEvent::new(&self.game_metadata.game, event, handlers);
+        bind.validate()?;
+        self.post("bind_game_event", &bind)
+    }
+
+    /// Load custom GoLisp handlers for the game, replacing all of its handlers
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the code.
+    pub fn load_golisp_handlers(&self, handlers: &GoLisp) -> Result<(), Error> {
+        self.post(
+            "load_golisp_handlers",
+            &json!({
+                "game": self.game_metadata.game,
+                "golisp": handlers.code()
+            }),
+        )
+    }
+
+    /// Send data for a custom event, independent of the other events
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the event.
+    pub fn send_event_data(&self, event: &str, data: serde_json::Value) -> Result<(), Error> {
+        self.post(
+            "game_event",
+            &GameEvent::new(&self.game_metadata.game, event, data),
+        )
+    }
+
+    /// Send an integer value for an event, as needed by the built-in visualizations of the
+    /// Engine (e.g. percent bars or counts) and by text lines showing the value
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the event.
+    pub fn send_event(&self, event: &str, value: i64) -> Result<(), Error> {
+        self.send_event_data(event, json!({ "value": value }))
+    }
+
+    /// Post a JSON body to any endpoint of the GameSense API, e.g. one this crate doesn't model
+    /// yet. Uses the resolved address, headers and client of this instance.
+    ///
+    /// ```ignore
+    /// api.send_raw("game_event", &json!({ "game": "MY_GAME", "event": "HEALTH", "data": { "value": 50 } }))?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub fn send_raw(&self, endpoint: &str, body: &serde_json::Value) -> Result<(), Error> {
+        self.post(endpoint.trim_start_matches('/'), body)
+    }
+
+    /// Send lines of text for an event bound with [`TextScreenHandler::lines`](crate::handlers::TextScreenHandler::lines)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the event.
+    pub fn send_text(&self, event: &str, lines: &[&str]) -> Result<(), Error> {
+        let frame: Map<String, serde_json::Value> = lines
+            .iter()
+            .enumerate()
+            .map(|(n, line)| (text_line_key(n), (*line).into()))
+            .collect();
+        self.send_context(event, &frame)
+    }
+
+    /// Send a context frame for an event, e.g. `{"song": "...", "cpu": 42}`. Text handlers show
+    /// its values with [`TextLine::context`](crate::handlers::TextLine::context).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the event.
+    pub fn send_context(
+        &self,
+        event: &str,
+        frame: &Map<String, serde_json::Value>,
+    ) -> Result<(), Error> {
+        self.send_event_data(event, json!({ "frame": frame }))
+    }
+
+    /// Send the content of the displays with a custom event bound with [`ScreenHandler`]s.
+    /// While the Engine is slow (see [`Config::slow_latency_ms`]), frames are sent at a reduced
+    /// rate and the frames in between are skipped. The latest skipped frame is still sent once
+    /// the reduced rate allows it, by the next call or by the heartbeat thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the heartbeat thread panicked while sending a skipped frame.
+    pub fn send_frame(&self, event: &str) -> Result<(), Error> {
+        let frame = self.frame(event);
+        if self
+            .counters
+            .throttle(Duration::from_millis(self.config.slow_frame_interval_ms))
+        {
+            let body =
+                serde_json::to_string(&frame).expect("GameSense requests are always serializable");
+            *self.pending_frame.lock().unwrap() = Some((event.to_string(), body));
+            return Ok(());
         }
-        let data = serde_json::to_string(&GameEvent {
-            event: DEFAULT_EVENT.to_string(),
-            game: self.game_metadata.game.clone(),
-            data: json!({
-                "frame": img_datas
+        self.post_frame(event, &frame)
+    }
+
+    // Helper which sends a frame regardless of the reduced frame rate, replacing a pending one
+    fn post_frame(&self, event: &str, frame: &GameEvent) -> Result<(), Error> {
+        self.pending_frame.lock().unwrap().take();
+        let result = self.post("game_event", frame);
+        self.counters.sent_frame(event, result.is_ok());
+        result
+    }
+
+    /// Send all events of a batch in a single request, so either all of them are delivered or
+    /// none is. Frames in the batch are sent regardless of the reduced frame rate while the Engine
+    /// is slow.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is still not reachable after the retries of the batch or
+    /// rejects the events.
+    pub fn send_batch(&self, batch: &Batch) -> Result<(), Error> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+        let game = &self.game_metadata.game;
+        let events: Vec<serde_json::Value> = batch
+            .events
+            .iter()
+            .map(|event| match event {
+                BatchEvent::Data(event, data) => json!({ "event": event, "data": data }),
+                BatchEvent::Frame(event) => json!({
+                    "event": event,
+                    "data": self.frame(event).data
+                }),
+            })
+            .collect();
+        let frames: Vec<&str> = batch
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                BatchEvent::Frame(event) => Some(event.as_str()),
+                BatchEvent::Data(..) => None,
+            })
+            .collect();
+        let body = json!({ "game": game, "events": events });
+
+        let mut attempts = 0;
+        let result = loop {
+            match self.post("multiple_game_events", &body) {
+                Err(Error::Http(_)) if attempts < batch.retries => attempts += 1,
+                result => break result,
+            }
+        };
+        for event in frames {
+            self.counters.sent_frame(event, result.is_ok());
+        }
+        result
+    }
+
+    /// Statistics of the current session, see [`SessionStats`]
+    #[must_use]
+    pub fn stats(&self) -> SessionStats {
+        self.counters.snapshot()
+    }
+
+    /// Which event the displays show and whether they are actively updated, e.g. to publish a
+    /// busy/away status in other tools
+    #[must_use]
+    pub fn activity(&self) -> Activity {
+        self.counters.activity()
+    }
+
+    /// Whether the Engine is reachable, e.g. to show the connectivity in the app or to wait with
+    /// drawing until the Engine is up. Sends a heartbeat, which also keeps the screen alive. An
+    /// Engine which rejects the heartbeat (e.g. because the game isn't registered yet) still
+    /// counts as alive.
+    #[must_use]
+    pub fn is_engine_alive(&self) -> bool {
+        let result = self.post(
+            "game_heartbeat",
+            &json!({
+                "game": self.game_metadata.game
             }),
-        })
-        .unwrap();
-        let res = self
-            .client
-            .post(format!("http://{}/game_event", self.address))
-            .body(data)
-            .headers((*self.headers).clone())
-            .send();
-        check_response(res.unwrap());
+        );
+        matches!(result, Ok(()) | Err(Error::EngineRejected { .. }))
+    }
+
+    /// Whether the frame rate is currently reduced because the Engine responds slowly. Useful to
+    /// show a status indicator or to reduce the work of the app as well.
+    #[must_use]
+    pub fn is_degraded(&self) -> bool {
+        self.counters.snapshot().degraded
+    }
+
+    /// Call `hook` whenever the frame rate is reduced because the Engine responds slowly (with
+    /// `true`) and when it recovers (with `false`), see [`GameSenseAPI::is_degraded`]
+    pub fn on_degradation(&mut self, hook: impl Fn(bool) + Send + Sync + 'static) {
+        self.on_degradation = Some(Box::new(hook));
+    }
+
+    /// Re-establish the connection to the Engine, e.g. after the system woke up from sleep:
+    /// re-reads the Engine address, registers the game, binds the event and sends the current
+    /// content of the displays again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine can't be found or any of the requests fails.
+    pub fn reconnect(&mut self) -> Result<(), Error> {
+        self.counters.reconnected();
+        self.set_address(resolve_address(&self.config)?);
+        self.register()?;
+        self.bind_event()?;
+        self.update_displays()?;
+        Ok(())
+    }
+
+    /// Call this method to update the screens. The content of all displays is sent in a single
+    /// event, so all devices show the same tick.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the frame.
+    pub fn update_displays(&self) -> Result<(), Error> {
+        self.send_frame(DEFAULT_EVENT)
     }
 
-    /// 128x40 display for Apex7, Apex 7 TKL, Apex Pro and Apex Pro TKL.
-    pub fn display_apex_mut(&mut self) -> &mut SteelSeriesDisplay {
-        self.displays.get_mut(&SteelSeriesLCDType::Apex).unwrap()
+    /// The display of a device type, `None` if it is not managed (see `Config::displays`)
+    pub fn display_mut(&mut self, lcd_type: SteelSeriesLCDType) -> Option<&mut SteelSeriesDisplay> {
+        self.displays.get_mut(&lcd_type)
     }
 
-    /// 128x48 display for Arctis Pro Wireless
-    pub fn display_arctis_mut(&mut self) -> &mut SteelSeriesDisplay {
-        self.displays.get_mut(&SteelSeriesLCDType::Arctis).unwrap()
+    /// 128x40 display for Apex7, Apex 7 TKL, Apex Pro and Apex Pro TKL, `None` if it is not
+    /// managed
+    pub fn display_apex_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::Apex)
     }
 
-    /// 128x52 display for GameDAC or Arctis Pro
-    pub fn display_game_dac_mut(&mut self) -> &mut SteelSeriesDisplay {
-        self.displays.get_mut(&SteelSeriesLCDType::GameDAC).unwrap()
+    /// 128x48 display for Arctis Pro Wireless, `None` if it is not managed
+    pub fn display_arctis_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::Arctis)
     }
 
-    /// 128x36 display for Rival 700 and 710
-    pub fn display_rival_mut(&mut self) -> &mut SteelSeriesDisplay {
-        self.displays
-            .get_mut(&SteelSeriesLCDType::Rival7x0)
-            .unwrap()
+    /// 128x52 display for GameDAC or Arctis Pro, `None` if it is not managed
+    pub fn display_game_dac_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::GameDAC)
+    }
+
+    /// 128x36 display for Rival 700 and 710, `None` if it is not managed
+    pub fn display_rival_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::Rival7x0)
+    }
+
+    /// Per-key lighting of keyboards, sent together with the frame of the displays so lighting
+    /// and screen content stay in sync. The first call enables the per-key lighting, call it
+    /// before `bind_event()` so the event is bound with a handler for it.
+    pub fn key_bitmap_mut(&mut self) -> &mut KeyBitmap {
+        self.key_bitmap.get_or_insert_with(KeyBitmap::new)
     }
 
     /// The GameSense API expects us to send a heartbeat every ~15seconds. Use this method to continously
-    /// send a heartbeat every 10 seconds.
+    /// send a heartbeat every 10 seconds (see `Config::heartbeat_interval_ms` and
+    /// `Config::heartbeat_jitter_ms`).
     /// Note that this is not required if you're updating the screen within the 15 seconds time interval
     /// If you send data only periodically, you should send the heartbeat in order to prevent the device
     /// from resetting the screen automatically.
-    pub fn register_heartbeat(&mut self) {
-        self.send_heartbeat = Arc::new(AtomicBool::new(true));
+    /// Heartbeats are skipped while frames are sent more often than the interval, as every frame
+    /// keeps the screen alive as well.
+    /// A heartbeat which is already running is stopped. The returned handle pauses, resumes and
+    /// stops the heartbeat and reports failed heartbeats.
+    #[allow(
+        clippy::missing_panics_doc,
+        reason = "only the heartbeat thread unwraps its locks"
+    )]
+    pub fn register_heartbeat(&mut self) -> HeartbeatHandle {
+        self.unregister_heartbeat();
+        let heartbeat = Arc::new(Heartbeat::new(self.address()));
+        self.heartbeat = Some(Arc::clone(&heartbeat));
+        let shared = Arc::clone(&heartbeat);
         let client = Arc::clone(&self.client);
-        let send_heartbeat = Arc::clone(&self.send_heartbeat);
-        let address = self.address.clone();
-        let data = serde_json::to_string(&json!({
+        let data = json!({
             "game": self.game_metadata.game
-        }))
-        .unwrap();
+        })
+        .to_string();
         let headers = (*self.headers).clone();
-        std::thread::spawn(move || {
-            while send_heartbeat.load(Ordering::Relaxed) {
-                let _ = client
-                    .post(format!("http://{address}/game_heartbeat"))
+        let config = self.config.clone();
+        let counters = Arc::clone(&self.counters);
+        let pending_frame = Arc::clone(&self.pending_frame);
+        let slow_frame_interval = Duration::from_millis(config.slow_frame_interval_ms);
+        let thread = std::thread::spawn(move || {
+            while heartbeat.is_running() {
+                // a frame skipped while the Engine was slow is sent as soon as it is due
+                let due = pending_frame
+                    .lock()
+                    .unwrap()
+                    .is_some()
+                    .then(|| counters.next_frame_in(slow_frame_interval));
+                if due == Some(Duration::ZERO)
+                    && let Some((event, body)) = pending_frame.lock().unwrap().take()
+                {
+                    let result = client
+                        .post(format!("http://{}/game_event", heartbeat.address()))
+                        .body(body)
+                        .headers(headers.clone())
+                        .send();
+                    let accepted = result.is_ok_and(|res| res.status().is_success());
+                    counters.sent_frame(&event, accepted);
+                    continue;
+                }
+                let wait =
+                    |timeout: Duration| heartbeat.wait(due.map_or(timeout, |due| due.min(timeout)));
+                let delay = config.heartbeat_delay();
+                if heartbeat.is_paused() {
+                    wait(delay);
+                    continue;
+                }
+                if let Some(elapsed) = counters.activity().since_last_frame
+                    && elapsed < delay
+                {
+                    wait(delay.saturating_sub(elapsed));
+                    continue;
+                }
+                let result = client
+                    .post(format!("http://{}/game_heartbeat", heartbeat.address()))
                     .body(data.clone())
                     .headers(headers.clone())
                     .send();
-                std::thread::sleep(Duration::from_secs(10));
+                if let Err(err) = &result
+                    && err.is_connect()
+                    && let Some(moved) = moved_address(&config, &heartbeat.address())
+                {
+                    heartbeat.set_address(moved);
+                }
+                heartbeat.report(result.map_err(Error::from).and_then(check_response));
+                wait(delay);
             }
         });
+        HeartbeatHandle::new(shared, thread)
     }
 
     /// Stop sending the heartbeat
     pub fn unregister_heartbeat(&mut self) {
-        self.send_heartbeat.store(false, Ordering::Relaxed);
+        if let Some(heartbeat) = self.heartbeat.take() {
+            heartbeat.stop();
+        }
+    }
+
+    // Helper which creates an event with the content of the displays and the key bitmap
+    fn frame(&self, event: &str) -> GameEvent {
+        let mut frame = GameEvent::frame(&self.game_metadata.game, event, &self.displays);
+        if let Some(bitmap) = &self.key_bitmap {
+            frame.data["frame"]["bitmap"] = bitmap.to_json();
+        }
+        frame
+    }
+
+    // Helper which reads the current address of the GameSense API
+    fn address(&self) -> String {
+        self.address.lock().unwrap().clone()
+    }
+
+    // Helper which switches all requests, including the heartbeat, to a new address
+    fn set_address(&self, address: String) {
+        if let Some(heartbeat) = &self.heartbeat {
+            heartbeat.set_address(address.clone());
+        }
+        *self.address.lock().unwrap() = address;
+    }
+
+    // Helper which posts a JSON body to an endpoint of the GameSense API
+    fn post<T: Serialize>(&self, endpoint: &str, body: &T) -> Result<(), Error> {
+        let data = serde_json::to_string(body).expect("GameSense requests are always serializable");
+        if data.len() > self.config.max_payload_bytes {
+            return Err(Error::PayloadTooLarge {
+                size: data.len(),
+                limit: self.config.max_payload_bytes,
+            });
+        }
+        self.counters.sent_bytes(data.len());
+        let started = Instant::now();
+        let send = |address: &str| {
+            self.client
+                .post(format!("http://{address}/{endpoint}"))
+                .body(data.clone())
+                .headers((*self.headers).clone())
+                .send()
+        };
+        let address = self.address();
+        let mut res = send(&address);
+        // SteelSeries GG picks a new port whenever it restarts
+        if let Err(err) = &res
+            && err.is_connect()
+            && let Some(moved) = moved_address(&self.config, &address)
+        {
+            self.set_address(moved.clone());
+            res = send(&moved);
+        }
+        if let Some(degraded) = self.counters.measured(
+            started.elapsed(),
+            Duration::from_millis(self.config.slow_latency_ms),
+        ) && let Some(hook) = &self.on_degradation
+        {
+            hook(degraded);
+        }
+        check_response(res?)
     }
 }
 
-// Helper which panics if the response of the REST request is not 200
-fn check_response(res: Response) {
-    assert!(
-        res.status().is_success(),
-        "Request failed! {:?}",
-        res.text().unwrap()
-    );
+// Helper which computes the size of a request body as sent by `post()`
+fn payload_size<T: Serialize>(body: &T) -> usize {
+    serde_json::to_string(body)
+        .expect("GameSense requests are always serializable")
+        .len()
+}
+
+// Helper which turns a response of the REST request which is not 200 into an error
+fn check_response(res: Response) -> Result<(), Error> {
+    let status = res.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    Err(rejection(status.as_u16(), res.text().unwrap_or_default()))
+}
+
+// Helper which builds the error for a rejected request from the body of the response
+pub(crate) fn rejection(status: u16, text: String) -> Error {
+    // the Engine usually responds with `{"error": "..."}`
+    let message = serde_json::from_str::<serde_json::Value>(&text)
+        .ok()
+        .and_then(|body| body.get("error")?.as_str().map(str::to_string))
+        .unwrap_or(text);
+    Error::EngineRejected { status, message }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use embedded_graphics::primitives::{PrimitiveStyle, Rectangle};
+
+    use super::*;
+    use crate::mock::{MockEngine, RecordedRequest};
+
+    // Helper which connects an API with an Apex display to the mock Engine
+    fn api(engine: &MockEngine, max_payload_bytes: usize) -> GameSenseAPI {
+        let config = Config {
+            displays: vec![SteelSeriesLCDType::Apex],
+            max_payload_bytes,
+            ..engine.config()
+        };
+        GameSenseAPI::with_config("TEST".to_string(), config)
+    }
+
+    // Helper which binds an animation of `count` frames, each with a bar of another width
+    fn bind(api: &GameSenseAPI, count: u32) -> Result<Vec<(String, Duration)>, Error> {
+        let mut frames: Vec<_> = (1..=count)
+            .map(|width| {
+                move |display: &mut SteelSeriesDisplay| {
+                    let _ = Rectangle::new(Point::zero(), Size::new(width, 40))
+                        .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                        .draw(display);
+                }
+            })
+            .collect();
+        let mut frames: Vec<&mut dyn FrameSource> = frames
+            .iter_mut()
+            .map(|frame| frame as &mut dyn FrameSource)
+            .collect();
+        api.bind_animation(
+            "INTRO",
+            &mut frames,
+            Duration::from_millis(100),
+            Repeat::Forever,
+        )
+    }
+
+    // Helper which returns the `datas` of the bound handlers
+    fn datas(request: &RecordedRequest) -> Vec<serde_json::Value> {
+        request.body["handlers"][0]["datas"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn small_animations_are_bound_to_one_event() {
+        let engine = MockEngine::start().unwrap();
+        let parts = bind(&api(&engine, 256 * 1024), 3).unwrap();
+
+        assert_eq!(parts, [("INTRO".to_string(), Duration::from_millis(300))]);
+        let requests = engine.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].endpoint, "bind_game_event");
+        assert_eq!(datas(&requests[0]).len(), 3);
+    }
+
+    #[test]
+    fn large_animations_are_split() {
+        let engine = MockEngine::start().unwrap();
+        let limit = 8 * 1024;
+        let parts = bind(&api(&engine, limit), 10).unwrap();
+
+        let requests = engine.requests();
+        assert!(parts.len() > 1);
+        assert_eq!(requests.len(), parts.len());
+        let mut frames = 0;
+        for (index, ((event, duration), request)) in parts.iter().zip(&requests).enumerate() {
+            let expected = match index {
+                0 => "INTRO".to_string(),
+                index => format!("INTRO-{}", index + 1),
+            };
+            assert_eq!(event, &expected);
+            assert_eq!(request.body["event"], expected.as_str());
+            assert!(payload_size(&request.body) <= limit);
+
+            let datas = datas(request);
+            let count = u32::try_from(datas.len()).unwrap();
+            assert_eq!(*duration, Duration::from_millis(100) * count);
+            // only the last part repeats
+            let last = index == parts.len() - 1;
+            assert_eq!(datas.last().unwrap().get("repeats").is_some(), last);
+            frames += datas.len();
+        }
+        assert_eq!(frames, 10);
+    }
+
+    #[test]
+    fn frames_larger_than_the_limit_are_rejected() {
+        let engine = MockEngine::start().unwrap();
+        let result = bind(&api(&engine, 1024), 3);
+
+        assert!(matches!(
+            result,
+            Err(Error::PayloadTooLarge { size, limit: 1024 }) if size > 1024
+        ));
+        assert!(engine.requests().is_empty());
+    }
 }
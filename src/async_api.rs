@@ -0,0 +1,325 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use reqwest::{Client, header::CONTENT_TYPE};
+use serde::Serialize;
+use serde_json::json;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+
+use crate::{
+    api::{
+        BindGameEvent, DEFAULT_EVENT, GameEvent, GameMetadata, moved_address, rejection,
+        resolve_address,
+    },
+    config::Config,
+    display::{SteelSeriesDisplay, SteelSeriesLCDType},
+    error::Error,
+    events::EventBuilder,
+};
+
+/// Async variant of [`GameSenseAPI`](crate::GameSenseAPI) for apps running on tokio
+///
+/// Works like the blocking API, but `register()`, `bind_event()` and `update_displays()` are
+/// async and the heartbeat is a tokio task instead of a thread.
+///
+/// Only available with the `async` feature.
+pub struct AsyncGameSenseAPI {
+    game_metadata: GameMetadata,
+    client: Client,
+    address: Arc<Mutex<String>>,
+    displays: HashMap<SteelSeriesLCDType, SteelSeriesDisplay>,
+    heartbeat: Option<JoinHandle<()>>,
+    config: Config,
+}
+
+impl AsyncGameSenseAPI {
+    /// Create a new instance of the async GameSense API, see [`GameSenseAPI::new`](crate::GameSenseAPI::new)
+    ///
+    /// # Arguments
+    ///
+    /// * `game_name` - A game name which will be shown in the SteelSeries Desktop Application. Allowed are upper-case A-Z, 0-9, hyphen, and underscore.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EngineNotFound`] if SteelSeries Engine can't be found.
+    pub fn try_new(game_name: String) -> Result<AsyncGameSenseAPI, Error> {
+        Self::try_with_config(game_name, Config::default())
+    }
+
+    /// Create a new instance of the async GameSense API with custom settings, see [`Config`].
+    /// `SSSCREEN_*` environment variables take precedence over the given config.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EngineNotFound`] if SteelSeries Engine can't be found or [`Error::Http`]
+    /// if the HTTP client could not be created.
+    pub fn try_with_config(
+        game_name: String,
+        mut config: Config,
+    ) -> Result<AsyncGameSenseAPI, Error> {
+        config.apply_env();
+        let displays = config
+            .displays
+            .iter()
+            .map(|lcd_type| (*lcd_type, SteelSeriesDisplay::new(*lcd_type)))
+            .collect();
+        let client = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()?;
+
+        Ok(AsyncGameSenseAPI {
+            game_metadata: GameMetadata::new(game_name, &config),
+            client,
+            address: Arc::new(Mutex::new(resolve_address(&config)?)),
+            displays,
+            heartbeat: None,
+            config,
+        })
+    }
+
+    /// Optionally set a developer name for this game. Will be shown in SteelSeries GG Client.
+    pub fn developer(&mut self, developer: String) {
+        self.game_metadata.developer = Some(developer);
+    }
+
+    /// Optionally set a game description for this game. Will be shown in SteelSeries GG Client.
+    pub fn game_description(&mut self, description: String) {
+        self.game_metadata.game_display_name = Some(description);
+    }
+
+    /// Register our game to the GameSense API.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the registration.
+    pub async fn register(&self) -> Result<(), Error> {
+        self.post("game_metadata", &self.game_metadata).await
+    }
+
+    /// Register the metadata of a custom event, see
+    /// [`GameSenseAPI::register_game_event`](crate::GameSenseAPI::register_game_event)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the event.
+    pub async fn register_game_event(&self, event: &EventBuilder) -> Result<(), Error> {
+        self.post(
+            "register_game_event",
+            &event.to_json(&self.game_metadata.game),
+        )
+        .await
+    }
+
+    /// Whether the Engine is reachable, see
+    /// [`GameSenseAPI::is_engine_alive`](crate::GameSenseAPI::is_engine_alive)
+    pub async fn is_engine_alive(&self) -> bool {
+        let result = self
+            .post(
+                "game_heartbeat",
+                &json!({
+                    "game": self.game_metadata.game
+                }),
+            )
+            .await;
+        matches!(result, Ok(()) | Err(Error::EngineRejected { .. }))
+    }
+
+    /// Tell the Engine that the game stopped, so the devices return to their default screen
+    /// immediately instead of waiting for the timeout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub async fn stop_game(&self) -> Result<(), Error> {
+        self.post(
+            "stop_game",
+            &json!({
+                "game": self.game_metadata.game
+            }),
+        )
+        .await
+    }
+
+    /// Remove the game and all of its events from SteelSeries GG.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub async fn remove_game(&self) -> Result<(), Error> {
+        self.post(
+            "remove_game",
+            &json!({
+                "game": self.game_metadata.game
+            }),
+        )
+        .await
+    }
+
+    /// Remove a previously bound event, e.g. when the handler layout changed between versions
+    /// of the app.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the request.
+    pub async fn remove_game_event(&self, event: &str) -> Result<(), Error> {
+        self.post(
+            "remove_game_event",
+            &json!({
+                "game": self.game_metadata.game,
+                "event": event
+            }),
+        )
+        .await
+    }
+
+    /// Bind the UPDATE event. This must be called AFTER the registration of the game.
+    /// The handlers are initialized with the current content of the displays.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the handlers.
+    pub async fn bind_event(&self) -> Result<(), Error> {
+        self.post(
+            "bind_game_event",
+            &BindGameEvent::screens(&self.game_metadata.game, &self.displays),
+        )
+        .await
+    }
+
+    /// Call this method to update the screens.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Engine is not reachable or rejects the frame.
+    pub async fn update_displays(&self) -> Result<(), Error> {
+        self.post(
+            "game_event",
+            &GameEvent::frame(&self.game_metadata.game, DEFAULT_EVENT, &self.displays),
+        )
+        .await
+    }
+
+    /// The display of a device type, `None` if it is not managed (see `Config::displays`)
+    pub fn display_mut(&mut self, lcd_type: SteelSeriesLCDType) -> Option<&mut SteelSeriesDisplay> {
+        self.displays.get_mut(&lcd_type)
+    }
+
+    /// 128x40 display for Apex7, Apex 7 TKL, Apex Pro and Apex Pro TKL, `None` if it is not
+    /// managed
+    pub fn display_apex_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::Apex)
+    }
+
+    /// 128x48 display for Arctis Pro Wireless, `None` if it is not managed
+    pub fn display_arctis_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::Arctis)
+    }
+
+    /// 128x52 display for GameDAC or Arctis Pro, `None` if it is not managed
+    pub fn display_game_dac_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::GameDAC)
+    }
+
+    /// 128x36 display for Rival 700 and 710, `None` if it is not managed
+    pub fn display_rival_mut(&mut self) -> Option<&mut SteelSeriesDisplay> {
+        self.display_mut(SteelSeriesLCDType::Rival7x0)
+    }
+
+    /// Spawn a tokio task which sends a heartbeat every `Config::heartbeat_interval_ms`, driven by
+    /// `tokio::time::interval`. With `Config::heartbeat_jitter_ms`, the timer runs faster by the
+    /// jitter and every heartbeat is delayed by a random part of it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a tokio runtime.
+    pub fn register_heartbeat(&mut self) {
+        self.unregister_heartbeat();
+        let client = self.client.clone();
+        let address = Arc::clone(&self.address);
+        let data = json!({
+            "game": self.game_metadata.game
+        })
+        .to_string();
+        let config = self.config.clone();
+        self.heartbeat = Some(tokio::spawn(async move {
+            let mut interval = tokio::time::interval(config.heartbeat_period());
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                tokio::time::sleep(config.heartbeat_jitter()).await;
+                let current = address.lock().unwrap().clone();
+                let result = client
+                    .post(format!("http://{current}/game_heartbeat"))
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(data.clone())
+                    .send()
+                    .await;
+                // SteelSeries GG picks a new port whenever it restarts
+                if let Err(err) = &result
+                    && err.is_connect()
+                    && let Some(moved) = moved_address(&config, &current)
+                {
+                    *address.lock().unwrap() = moved;
+                }
+            }
+        }));
+    }
+
+    /// Stop sending the heartbeat
+    pub fn unregister_heartbeat(&mut self) {
+        if let Some(heartbeat) = self.heartbeat.take() {
+            heartbeat.abort();
+        }
+    }
+
+    // Helper which reads the current address of the GameSense API
+    fn address(&self) -> String {
+        self.address.lock().unwrap().clone()
+    }
+
+    // Helper which posts a JSON body to an endpoint of the GameSense API
+    async fn post<T: Serialize>(&self, endpoint: &str, body: &T) -> Result<(), Error> {
+        let data = serde_json::to_string(body).expect("GameSense requests are always serializable");
+        if data.len() > self.config.max_payload_bytes {
+            return Err(Error::PayloadTooLarge {
+                size: data.len(),
+                limit: self.config.max_payload_bytes,
+            });
+        }
+        let send = |address: String| {
+            self.client
+                .post(format!("http://{address}/{endpoint}"))
+                .header(CONTENT_TYPE, "application/json")
+                .body(data.clone())
+                .send()
+        };
+        let address = self.address();
+        let mut res = send(address.clone()).await;
+        // SteelSeries GG picks a new port whenever it restarts
+        if let Err(err) = &res
+            && err.is_connect()
+            && let Some(moved) = moved_address(&self.config, &address)
+        {
+            self.address.lock().unwrap().clone_from(&moved);
+            res = send(moved).await;
+        }
+        let res = res?;
+        let status = res.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        Err(rejection(
+            status.as_u16(),
+            res.text().await.unwrap_or_default(),
+        ))
+    }
+}
+
+impl Drop for AsyncGameSenseAPI {
+    fn drop(&mut self) {
+        self.unregister_heartbeat();
+    }
+}
@@ -0,0 +1,200 @@
+//! Patterns which draw attention to the display
+//!
+//! An [`Attention`] pattern is a short animation which is applied on top of whatever the display
+//! currently shows, so notifications, [alert rules](crate::alert) and apps can all trigger the
+//! same patterns by name:
+//!
+//! ```ignore
+//! let mut effect = "double-flash".parse::<Attention>()?.play(Duration::from_secs(3));
+//! while let Some(display) = api.display_apex_mut() {
+//!     draw_dashboard(display);
+//!     effect.apply(display);
+//!     api.update_displays()?;
+//! }
+//! ```
+
+use std::{
+    fmt,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+};
+
+use crate::{display::SteelSeriesDisplay, widgets::dither};
+
+/// The named attention patterns
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Attention {
+    /// Invert the whole screen twice in quick succession, every 1.5 seconds
+    DoubleFlash,
+    /// Fade a dithered overlay in and out, every 2 seconds
+    SlowPulse,
+    /// Blink a frame around the edge of the screen twice per second
+    BorderBlink,
+    /// Blink a small square in the top-right corner once per second
+    CornerBeacon,
+}
+
+impl Attention {
+    /// All patterns, e.g. to list them in a settings menu
+    #[must_use]
+    pub fn all() -> &'static [Attention] {
+        &[
+            Attention::DoubleFlash,
+            Attention::SlowPulse,
+            Attention::BorderBlink,
+            Attention::CornerBeacon,
+        ]
+    }
+
+    /// The name of the pattern, as accepted by `parse()`
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Attention::DoubleFlash => "double-flash",
+            Attention::SlowPulse => "slow-pulse",
+            Attention::BorderBlink => "border-blink",
+            Attention::CornerBeacon => "corner-beacon",
+        }
+    }
+
+    /// Start playing the pattern for `duration`
+    #[must_use]
+    pub fn play(self, duration: Duration) -> AttentionEffect {
+        self.play_at(Instant::now(), duration)
+    }
+
+    /// Start playing the pattern at the given point in time (e.g. `clock.instant()` of a
+    /// [`Clock`](crate::Clock)). Use the same time source for
+    /// [`AttentionEffect::apply_at`] and [`AttentionEffect::is_finished_at`].
+    #[must_use]
+    pub fn play_at(self, start: Instant, duration: Duration) -> AttentionEffect {
+        AttentionEffect {
+            pattern: self,
+            started: start,
+            duration,
+        }
+    }
+
+    /// Apply the pattern as it looks `elapsed` after it started onto the display
+    pub fn apply_frame(&self, display: &mut SteelSeriesDisplay, elapsed: Duration) {
+        let millis = elapsed.as_millis();
+        let area = display.bounding_box();
+        match self {
+            Attention::DoubleFlash => {
+                let phase = millis % 1500;
+                if phase < 150 || (300..450).contains(&phase) {
+                    invert(display, |_, _| true);
+                }
+            }
+            Attention::SlowPulse => {
+                #[allow(clippy::cast_precision_loss)]
+                let phase = (millis % 2000) as f32 / 2000.0;
+                let intensity = (1.0 - (phase * std::f32::consts::TAU).cos()) / 4.0;
+                invert(display, |x, y| dither(intensity, x, y));
+            }
+            Attention::BorderBlink => {
+                if millis % 500 < 250 {
+                    let _ = area
+                        .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 2))
+                        .draw(display);
+                }
+            }
+            Attention::CornerBeacon => {
+                if millis % 1000 < 250 {
+                    let beacon = Rectangle::new(
+                        Point::new(area.size.width.cast_signed() - 8, 2),
+                        Size::new(6, 6),
+                    );
+                    let _ = beacon
+                        .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                        .draw(display);
+                }
+            }
+        }
+    }
+}
+
+// Helper which inverts all pixels for which `select(x, y)` is true
+fn invert(display: &mut SteelSeriesDisplay, select: impl Fn(u32, u32) -> bool) {
+    let size = display.size();
+    for y in 0..size.height {
+        for x in 0..size.width {
+            if !select(x, y) {
+                continue;
+            }
+            let point = Point::new(x.cast_signed(), y.cast_signed());
+            if let Some(color) = display.pixel(point) {
+                let _ = Pixel(point, color.invert()).draw(display);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Attention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Attention {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Attention::all()
+            .iter()
+            .find(|pattern| pattern.name() == name)
+            .copied()
+            .ok_or_else(|| format!("Unknown attention pattern: {name}"))
+    }
+}
+
+/// An attention pattern which is played for a limited time
+pub struct AttentionEffect {
+    pattern: Attention,
+    started: Instant,
+    duration: Duration,
+}
+
+impl AttentionEffect {
+    /// The pattern which is played
+    #[must_use]
+    pub fn pattern(&self) -> Attention {
+        self.pattern
+    }
+
+    /// Whether the effect is over
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.is_finished_at(Instant::now())
+    }
+
+    /// Whether the effect is over at the given point in time, see [`Attention::play_at`]
+    #[must_use]
+    pub fn is_finished_at(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.started) >= self.duration
+    }
+
+    /// Apply the current frame of the pattern onto the display, which should already show the
+    /// regular content. Returns false once the effect is over and leaves the display untouched.
+    pub fn apply(&self, display: &mut SteelSeriesDisplay) -> bool {
+        self.apply_at(display, Instant::now())
+    }
+
+    /// Apply the frame of the pattern at the given point in time (e.g. `clock.instant()` of a
+    /// [`Clock`](crate::Clock) the effect was started with, see [`Attention::play_at`]), see
+    /// [`AttentionEffect::apply`]
+    pub fn apply_at(&self, display: &mut SteelSeriesDisplay, now: Instant) -> bool {
+        if self.is_finished_at(now) {
+            return false;
+        }
+        self.pattern
+            .apply_frame(display, now.saturating_duration_since(self.started));
+        true
+    }
+}
@@ -0,0 +1,68 @@
+use serde_json::json;
+
+// A single event of a batch, the frame is taken from the displays when the batch is sent
+pub(crate) enum BatchEvent {
+    Data(String, serde_json::Value),
+    Frame(String),
+}
+
+/// Several game events sent together with [`GameSenseAPI::send_batch`](crate::GameSenseAPI::send_batch)
+///
+/// The events are sent in a single request, so either all of them reach the Engine or none does.
+///
+/// ```ignore
+/// let batch = Batch::new()
+///     .frame("SCREEN")
+///     .value("KILLS", 12)
+///     .value("BUZZ", 1)
+///     .retries(2);
+/// api.send_batch(&batch)?;
+/// ```
+#[derive(Default)]
+pub struct Batch {
+    pub(crate) events: Vec<BatchEvent>,
+    pub(crate) retries: u32,
+}
+
+impl Batch {
+    /// Create an empty batch
+    #[must_use]
+    pub fn new() -> Batch {
+        Batch::default()
+    }
+
+    /// Add an event with custom data
+    #[must_use]
+    pub fn event(mut self, event: impl Into<String>, data: serde_json::Value) -> Self {
+        self.events.push(BatchEvent::Data(event.into(), data));
+        self
+    }
+
+    /// Add an event with a numeric value, e.g. for a stat or a tactile handler
+    #[must_use]
+    pub fn value(self, event: impl Into<String>, value: i64) -> Self {
+        self.event(event, json!({ "value": value }))
+    }
+
+    /// Add an event carrying the content of the displays at the time the batch is sent, bound
+    /// with [`ScreenHandler`](crate::handlers::ScreenHandler)s
+    #[must_use]
+    pub fn frame(mut self, event: impl Into<String>) -> Self {
+        self.events.push(BatchEvent::Frame(event.into()));
+        self
+    }
+
+    /// Resend the whole batch up to `retries` times if it could not be delivered (defaults to 0).
+    /// Batches rejected by the Engine are not retried.
+    #[must_use]
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Whether the batch contains no events
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
@@ -0,0 +1,190 @@
+//! Shared memory limit for long-running apps
+//!
+//! Components which buffer data over time, like [`TextFile`](crate::widgets::TextFile),
+//! [`Transfers`](crate::widgets::Transfers) and the `GifRecorder` of the `capture` feature, can
+//! share a [`MemoryBudget`]. A component which would exceed the budget drops its oldest data, just
+//! like when it reaches its own capacity. The budget reports the usage of all of them:
+//!
+//! ```ignore
+//! let budget = MemoryBudget::new(4 * 1024 * 1024);
+//! let mut log = TextFile::new("status.log").budget(&budget);
+//! let mut recorder = GifRecorder::new(2).budget(&budget);
+//! // ...
+//! println!("{} of {} bytes used, peak {}", budget.used(), budget.limit(), budget.peak());
+//! ```
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+/// Memory limit shared by several components, cheap to clone
+#[derive(Clone, Debug)]
+pub struct MemoryBudget {
+    usage: Arc<Usage>,
+}
+
+#[derive(Debug)]
+struct Usage {
+    limit: usize,
+    used: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl MemoryBudget {
+    /// Create a budget of `limit` bytes
+    #[must_use]
+    pub fn new(limit: usize) -> MemoryBudget {
+        MemoryBudget {
+            usage: Arc::new(Usage {
+                limit,
+                used: AtomicUsize::new(0),
+                peak: AtomicUsize::new(0),
+            }),
+        }
+    }
+
+    /// Create a budget without a limit, which only tracks the usage
+    #[must_use]
+    pub fn unlimited() -> MemoryBudget {
+        MemoryBudget::new(usize::MAX)
+    }
+
+    /// The limit in bytes
+    #[must_use]
+    pub fn limit(&self) -> usize {
+        self.usage.limit
+    }
+
+    /// Bytes currently used by all components sharing the budget
+    #[must_use]
+    pub fn used(&self) -> usize {
+        self.usage.used.load(Ordering::Relaxed)
+    }
+
+    /// Highest usage so far in bytes
+    #[must_use]
+    pub fn peak(&self) -> usize {
+        self.usage.peak.load(Ordering::Relaxed)
+    }
+
+    /// Bytes left until the limit is reached
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.limit().saturating_sub(self.used())
+    }
+
+    // Helper which reserves `bytes` if they fit into the budget
+    fn try_reserve(&self, bytes: usize) -> bool {
+        let limit = self.usage.limit;
+        let reserved = self
+            .usage
+            .used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(bytes).filter(|total| *total <= limit)
+            });
+        match reserved {
+            Ok(previous) => {
+                self.usage
+                    .peak
+                    .fetch_max(previous + bytes, Ordering::Relaxed);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // Helper which returns previously reserved bytes
+    fn release(&self, bytes: usize) {
+        self.usage.used.fetch_sub(bytes, Ordering::Relaxed);
+    }
+}
+
+impl Default for MemoryBudget {
+    fn default() -> Self {
+        MemoryBudget::unlimited()
+    }
+}
+
+/// The bytes one component reserved from a [`MemoryBudget`], returned when it is dropped
+#[derive(Debug, Default)]
+pub(crate) struct Reservation {
+    budget: MemoryBudget,
+    bytes: usize,
+}
+
+impl Reservation {
+    /// Start an empty reservation of `budget`
+    pub(crate) fn new(budget: &MemoryBudget) -> Reservation {
+        Reservation {
+            budget: budget.clone(),
+            bytes: 0,
+        }
+    }
+
+    /// Bytes currently reserved
+    pub(crate) fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    /// Reserve `bytes` more, false if they don't fit into the budget
+    pub(crate) fn grow(&mut self, bytes: usize) -> bool {
+        let reserved = self.budget.try_reserve(bytes);
+        if reserved {
+            self.bytes += bytes;
+        }
+        reserved
+    }
+
+    /// Return `bytes` of the reservation to the budget
+    pub(crate) fn shrink(&mut self, bytes: usize) {
+        let bytes = bytes.min(self.bytes);
+        self.budget.release(bytes);
+        self.bytes -= bytes;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        self.budget.release(self.bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::Transfers;
+
+    #[test]
+    fn reservations_are_limited_and_returned() {
+        let budget = MemoryBudget::new(100);
+        let mut first = Reservation::new(&budget);
+        let mut second = Reservation::new(&budget);
+        assert!(first.grow(60));
+        assert!(!second.grow(50));
+        assert!(second.grow(40));
+        assert_eq!((budget.used(), budget.remaining()), (100, 0));
+        first.shrink(30);
+        assert_eq!((first.bytes(), budget.used()), (30, 70));
+        drop(second);
+        assert_eq!((budget.used(), budget.peak()), (30, 100));
+    }
+
+    #[test]
+    fn components_drop_their_oldest_data() {
+        let mut single = Transfers::new();
+        single.update("a", 0, 10);
+        let budget = MemoryBudget::new(3 * single.memory_usage());
+        let mut transfers = Transfers::new().budget(&budget);
+        for name in ["a", "b", "c", "d"] {
+            transfers.update(name, 0, 10);
+        }
+        assert_eq!(transfers.len(), 3);
+        // the oldest transfer was dropped for the newest one
+        transfers.remove("a");
+        assert_eq!(transfers.len(), 3);
+        assert_eq!(budget.used(), transfers.memory_usage());
+        drop(transfers);
+        assert_eq!(budget.used(), 0);
+    }
+}
@@ -0,0 +1,150 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::{
+    api::GameSenseAPI, config::Config, display::SteelSeriesLCDType, error::Error,
+    frame_source::FrameSource,
+};
+
+/// Builder for a [`GameSenseAPI`], created with [`GameSenseAPI::builder`]
+///
+/// ```ignore
+/// let mut api = GameSenseAPI::builder("MY_DASHBOARD")
+///     .developer("Max")
+///     .game_display_name("My Dashboard")
+///     .displays(&[SteelSeriesLCDType::Apex])
+///     .connect()?;
+/// ```
+pub struct GameSenseAPIBuilder {
+    game_name: String,
+    config: Config,
+    splash: Option<Box<dyn FrameSource + Send + Sync>>,
+    goodbye: Option<Box<dyn FrameSource + Send + Sync>>,
+}
+
+impl GameSenseAPIBuilder {
+    pub(crate) fn new(game_name: String) -> GameSenseAPIBuilder {
+        GameSenseAPIBuilder {
+            game_name,
+            config: Config::default(),
+            splash: None,
+            goodbye: None,
+        }
+    }
+
+    /// Start from the given config instead of the defaults
+    #[must_use]
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Developer name shown in the SteelSeries GG Client
+    #[must_use]
+    pub fn developer(mut self, developer: impl Into<String>) -> Self {
+        self.config.developer = Some(developer.into());
+        self
+    }
+
+    /// Human readable name of the game shown in the SteelSeries GG Client
+    #[must_use]
+    pub fn game_display_name(mut self, name: impl Into<String>) -> Self {
+        self.config.game_display_name = Some(name.into());
+        self
+    }
+
+    /// Interval of the heartbeat (defaults to 10 seconds, kept below the deinitialize timer)
+    #[must_use]
+    pub fn heartbeat_interval(mut self, interval: Duration) -> Self {
+        self.config.heartbeat_interval_ms = u64::try_from(interval.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Send every heartbeat up to `jitter` earlier than the interval, at random (defaults to 0)
+    #[must_use]
+    pub fn heartbeat_jitter(mut self, jitter: Duration) -> Self {
+        self.config.heartbeat_jitter_ms = u64::try_from(jitter.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Time without events or heartbeats after which the Engine reverts the screen (1 to 60
+    /// seconds, defaults to the Engine's 15 seconds)
+    #[must_use]
+    pub fn deinitialize_timer(mut self, timer: Duration) -> Self {
+        self.config.deinitialize_timer_ms =
+            Some(u64::try_from(timer.as_millis()).unwrap_or(u64::MAX));
+        self
+    }
+
+    /// Timeout of every request to the Engine (defaults to 5 seconds)
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout_ms = u64::try_from(timeout.as_millis()).unwrap_or(u64::MAX);
+        self
+    }
+
+    /// Use a fixed address (`host:port`) instead of discovering it from coreProps.json
+    #[must_use]
+    pub fn address(mut self, address: impl Into<String>) -> Self {
+        self.config.address = Some(address.into());
+        self
+    }
+
+    /// Read the Engine address from a custom coreProps.json
+    #[must_use]
+    pub fn core_props_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config.core_props_path = Some(path.into());
+        self
+    }
+
+    /// The display types to manage (defaults to all of them)
+    #[must_use]
+    pub fn displays(mut self, displays: &[SteelSeriesLCDType]) -> Self {
+        self.config.displays = displays.to_vec();
+        self
+    }
+
+    /// Largest request body in bytes which is sent to the Engine (defaults to 256 KiB)
+    #[must_use]
+    pub fn max_payload_bytes(mut self, max: usize) -> Self {
+        self.config.max_payload_bytes = max;
+        self
+    }
+
+    /// Splash screen which is shown by [`GameSenseAPI::start`]
+    #[must_use]
+    pub fn splash<F: FrameSource + Send + Sync + 'static>(mut self, splash: F) -> Self {
+        self.splash = Some(Box::new(splash));
+        self
+    }
+
+    /// Frame which is shown by [`GameSenseAPI::shutdown`]
+    #[must_use]
+    pub fn goodbye<F: FrameSource + Send + Sync + 'static>(mut self, goodbye: F) -> Self {
+        self.goodbye = Some(Box::new(goodbye));
+        self
+    }
+
+    /// Create the API. `SSSCREEN_*` environment variables take precedence over the settings of
+    /// the builder. The game still needs to be registered, see [`GameSenseAPIBuilder::connect`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::EngineNotFound`] if SteelSeries Engine can't be found.
+    pub fn build(self) -> Result<GameSenseAPI, Error> {
+        let mut api = GameSenseAPI::try_with_config(self.game_name, self.config)?;
+        api.splash = self.splash;
+        api.goodbye = self.goodbye;
+        Ok(api)
+    }
+
+    /// Create the API, register the game and bind the event (see [`GameSenseAPI::start`])
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SteelSeries Engine can't be found or rejects the registration.
+    pub fn connect(self) -> Result<GameSenseAPI, Error> {
+        let mut api = self.build()?;
+        api.start()?;
+        Ok(api)
+    }
+}
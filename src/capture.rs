@@ -0,0 +1,224 @@
+//! Capturing rendered output as images
+//!
+//! Helpers to share dashboard designs without filming the keyboard:
+//! * [`GifRecorder`] records frames and exports them as an animated GIF
+//! * [`mockup`] composites a frame into a simple device bezel for screenshots
+//! * [`diff`] visualizes the pixels which changed between two frames
+//!
+//! All images are `image::RgbaImage`s, so they can be saved as PNG with `image.save("out.png")`.
+//!
+//! Only available with the `capture` feature.
+
+use std::{
+    collections::VecDeque,
+    fs::File,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+use image::{
+    Delay, Frame, ImageError, Rgba, RgbaImage,
+    codecs::gif::{GifEncoder, Repeat},
+};
+
+use crate::{
+    budget::{MemoryBudget, Reservation},
+    display::SteelSeriesDisplay,
+};
+
+const LIT: Rgba<u8> = Rgba([255, 255, 255, 255]);
+const DARK: Rgba<u8> = Rgba([0, 0, 0, 255]);
+const BEZEL: Rgba<u8> = Rgba([40, 40, 44, 255]);
+const ADDED: Rgba<u8> = Rgba([80, 220, 80, 255]);
+const REMOVED: Rgba<u8> = Rgba([230, 60, 60, 255]);
+const UNCHANGED: Rgba<u8> = Rgba([90, 90, 90, 255]);
+
+/// Render a display into an image, scaling every pixel up to a `scale`x`scale` block
+#[must_use]
+pub fn render(display: &SteelSeriesDisplay, scale: u32) -> RgbaImage {
+    let scale = scale.max(1);
+    let size = display.size();
+    RgbaImage::from_fn(size.width * scale, size.height * scale, |x, y| {
+        let point = Point::new((x / scale).cast_signed(), (y / scale).cast_signed());
+        match display.pixel(point) {
+            Some(BinaryColor::On) => LIT,
+            _ => DARK,
+        }
+    })
+}
+
+/// Composite a display into a device mockup: the scaled-up screen surrounded by a rounded bezel
+#[must_use]
+pub fn mockup(display: &SteelSeriesDisplay, scale: u32) -> RgbaImage {
+    let screen = render(display, scale);
+    let border = scale.max(1) * 6;
+    let (width, height) = (screen.width() + 2 * border, screen.height() + 2 * border);
+    let radius = border;
+    let mut image = RgbaImage::from_fn(width, height, |x, y| {
+        // transparent corners outside of the rounded bezel
+        let dx = radius
+            .saturating_sub(x)
+            .max((x + radius + 1).saturating_sub(width));
+        let dy = radius
+            .saturating_sub(y)
+            .max((y + radius + 1).saturating_sub(height));
+        if dx * dx + dy * dy > radius * radius {
+            Rgba([0, 0, 0, 0])
+        } else {
+            BEZEL
+        }
+    });
+    image::imageops::overlay(&mut image, &screen, i64::from(border), i64::from(border));
+    image
+}
+
+/// Visualize the difference between two frames of the same display type: pixels which were
+/// switched on are green, pixels which were switched off are red and unchanged lit pixels are gray.
+/// Handy to debug flicker and unintended redraws.
+#[must_use]
+pub fn diff(before: &SteelSeriesDisplay, after: &SteelSeriesDisplay, scale: u32) -> RgbaImage {
+    let scale = scale.max(1);
+    let size = after.size();
+    RgbaImage::from_fn(size.width * scale, size.height * scale, |x, y| {
+        let point = Point::new((x / scale).cast_signed(), (y / scale).cast_signed());
+        match (before.pixel(point), after.pixel(point)) {
+            (Some(BinaryColor::On), Some(BinaryColor::On)) => UNCHANGED,
+            (Some(BinaryColor::On), _) => REMOVED,
+            (_, Some(BinaryColor::On)) => ADDED,
+            _ => DARK,
+        }
+    })
+}
+
+// Helper which returns the memory of a recorded frame, as charged to the budget
+fn frame_bytes(display: &SteelSeriesDisplay) -> usize {
+    std::mem::size_of::<(SteelSeriesDisplay, Instant)>() + display.framebuffer.len()
+}
+
+/// Records frames and exports them as an animated GIF
+///
+/// Call [`GifRecorder::capture`] right after every `update_displays()`; the time between two
+/// captures becomes the delay of the frame in the GIF.
+pub struct GifRecorder {
+    scale: u32,
+    bezel: bool,
+    frames: VecDeque<(SteelSeriesDisplay, Instant)>,
+    limit: Duration,
+    memory: Reservation,
+}
+
+impl GifRecorder {
+    /// Create a recorder scaling every pixel up to a `scale`x`scale` block
+    #[must_use]
+    pub fn new(scale: u32) -> GifRecorder {
+        GifRecorder {
+            scale,
+            bezel: false,
+            frames: VecDeque::new(),
+            limit: Duration::from_secs(10),
+            memory: Reservation::default(),
+        }
+    }
+
+    /// Render the frames into a device mockup (see [`mockup`])
+    #[must_use]
+    pub fn bezel(mut self, bezel: bool) -> Self {
+        self.bezel = bezel;
+        self
+    }
+
+    /// Only keep the last `limit` of recorded time (defaults to 10 seconds)
+    #[must_use]
+    pub fn limit(mut self, limit: Duration) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Share a memory budget with other components (see [`MemoryBudget`]). Frames which exceed
+    /// it are dropped like the frames older than the limit, starting with the oldest one.
+    #[must_use]
+    pub fn budget(mut self, budget: &MemoryBudget) -> Self {
+        let mut memory = Reservation::new(budget);
+        self.frames
+            .retain(|(display, _)| memory.grow(frame_bytes(display)));
+        self.memory = memory;
+        self
+    }
+
+    /// Bytes used by the recorded frames
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.memory.bytes()
+    }
+
+    /// Record the current content of a display
+    pub fn capture(&mut self, display: &SteelSeriesDisplay) {
+        let now = Instant::now();
+        while !self.memory.grow(frame_bytes(display)) {
+            if !self.drop_oldest() {
+                println!("Warning: Frame exceeds the memory budget of the GifRecorder");
+                return;
+            }
+        }
+        self.frames.push_back((display.clone(), now));
+        while self
+            .frames
+            .front()
+            .is_some_and(|(_, captured)| now.duration_since(*captured) > self.limit)
+        {
+            self.drop_oldest();
+        }
+    }
+
+    // Helper which drops the oldest frame, false if there is none
+    fn drop_oldest(&mut self) -> bool {
+        let Some((display, _)) = self.frames.pop_front() else {
+            return false;
+        };
+        self.memory.shrink(frame_bytes(&display));
+        true
+    }
+
+    /// Number of recorded frames
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frame was recorded yet
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Write the recorded frames as an endlessly looping GIF
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be written or the frames could not be encoded.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ImageError> {
+        let mut encoder = GifEncoder::new(File::create(path)?);
+        encoder.set_repeat(Repeat::Infinite)?;
+        let frames = self
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(index, (display, captured))| {
+                // the last frame has no successor, show it for a moment before looping
+                let delay = self
+                    .frames
+                    .get(index + 1)
+                    .map_or(Duration::from_millis(500), |(_, next)| {
+                        next.duration_since(*captured)
+                    });
+                let image = if self.bezel {
+                    mockup(display, self.scale)
+                } else {
+                    render(display, self.scale)
+                };
+                Frame::from_parts(image, 0, 0, Delay::from_saturating_duration(delay))
+            });
+        encoder.encode_frames(frames)
+    }
+}
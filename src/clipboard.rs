@@ -0,0 +1,75 @@
+//! Preview of the current clipboard text
+//!
+//! Handy for keeping an eye on copied codes or IDs. With the privacy toggle enabled, only the last
+//! characters of the clipboard content are revealed.
+//!
+//! Only available with the `clipboard` feature.
+
+pub use arboard::Error;
+
+/// Number of characters which stay visible in private mode
+const VISIBLE_IN_PRIVATE_MODE: usize = 4;
+
+/// Watches the clipboard and provides a one-line preview of its text content
+pub struct ClipboardPreview {
+    clipboard: arboard::Clipboard,
+    text: Option<String>,
+    private: bool,
+}
+
+impl ClipboardPreview {
+    /// Open the system clipboard
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the clipboard of the system is not accessible.
+    pub fn new() -> Result<ClipboardPreview, Error> {
+        Ok(ClipboardPreview {
+            clipboard: arboard::Clipboard::new()?,
+            text: None,
+            private: false,
+        })
+    }
+
+    /// Only reveal the last characters of the clipboard text, masking the rest with `*`
+    #[must_use]
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+
+    /// Toggle the privacy mode at runtime
+    pub fn set_private(&mut self, private: bool) {
+        self.private = private;
+    }
+
+    /// Read the clipboard. Returns true if its text changed since the last poll, so the screen only
+    /// needs to be redrawn on change. Non-text content counts as an empty clipboard.
+    pub fn poll(&mut self) -> bool {
+        let text = self.clipboard.get_text().ok();
+        let changed = text != self.text;
+        self.text = text;
+        changed
+    }
+
+    /// The first line of the clipboard text as of the last [`ClipboardPreview::poll`]
+    #[must_use]
+    pub fn preview(&self) -> Option<String> {
+        let line = self
+            .text
+            .as_deref()?
+            .lines()
+            .find(|line| !line.trim().is_empty())?;
+        let line = line.trim();
+        if !self.private {
+            return Some(line.to_string());
+        }
+        let hidden = line.chars().count().saturating_sub(VISIBLE_IN_PRIVATE_MODE);
+        Some(
+            line.chars()
+                .enumerate()
+                .map(|(index, c)| if index < hidden { '*' } else { c })
+                .collect(),
+        )
+    }
+}
@@ -0,0 +1,134 @@
+use std::time::{Duration, Instant};
+
+/// Fixed-timestep clock for animations
+///
+/// The clock advances in fixed steps, so animations behave the same no matter how irregular the
+/// update loop runs. It can be paused and scaled (e.g. slow motion for debugging), and a manual
+/// clock which only advances when told to allows deterministic tests of animated content.
+///
+/// ```ignore
+/// let mut clock = Clock::new(Duration::from_millis(100));
+/// loop {
+///     for _ in 0..clock.tick() {
+///         // advance animations by one step
+///     }
+///     odometer.set_time(clock.instant());
+///     // draw and update_displays()
+/// }
+/// ```
+pub struct Clock {
+    step: Duration,
+    scale: f64,
+    paused: bool,
+    origin: Instant,
+    time: Duration,
+    accumulator: Duration,
+    last: Option<Instant>,
+}
+
+impl Clock {
+    /// Create a clock following the wall clock
+    /// # Arguments
+    ///
+    /// * `step` - Duration of a single tick, e.g. 100ms for 10 Hz animations
+    ///
+    #[must_use]
+    pub fn new(step: Duration) -> Clock {
+        let now = Instant::now();
+        Clock {
+            step: step.max(Duration::from_millis(1)),
+            scale: 1.0,
+            paused: false,
+            origin: now,
+            time: Duration::ZERO,
+            accumulator: Duration::ZERO,
+            last: Some(now),
+        }
+    }
+
+    /// Create a clock which only advances through [`Clock::advance`], for deterministic tests
+    #[must_use]
+    pub fn manual(step: Duration) -> Clock {
+        Clock {
+            last: None,
+            ..Clock::new(step)
+        }
+    }
+
+    /// Duration of a single tick
+    #[must_use]
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// Animation time elapsed since the clock was created, always a multiple of the step
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.time
+    }
+
+    /// The animation time as an `Instant`, for APIs which work with instants (e.g.
+    /// `Odometer::set_time`). Unlike `Instant::now()`, it respects pausing and scaling.
+    #[must_use]
+    pub fn instant(&self) -> Instant {
+        self.origin + self.time
+    }
+
+    /// Stop advancing the animation time
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continue advancing the animation time. Time passed while paused is skipped.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        if self.last.is_some() {
+            self.last = Some(Instant::now());
+        }
+    }
+
+    /// Whether the clock is paused
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Set the speed of the animation time relative to the wall clock (defaults to 1.0). Negative
+    /// and NaN scales stop the clock.
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = if scale.is_nan() {
+            0.0
+        } else {
+            scale.clamp(0.0, f64::MAX)
+        };
+    }
+
+    /// Advance the clock by the wall-clock time passed since the last call. Returns the number of
+    /// whole steps which elapsed. Manual clocks are not affected.
+    pub fn tick(&mut self) -> u32 {
+        let Some(last) = self.last else {
+            return 0;
+        };
+        let now = Instant::now();
+        self.last = Some(now);
+        self.advance(now.saturating_duration_since(last))
+    }
+
+    /// Advance the clock by `delta` of wall-clock time. Returns the number of whole steps which
+    /// elapsed.
+    pub fn advance(&mut self, delta: Duration) -> u32 {
+        if self.paused {
+            return 0;
+        }
+        // huge scales would overflow the duration, they saturate instead
+        let scaled =
+            Duration::try_from_secs_f64(delta.as_secs_f64() * self.scale).unwrap_or(Duration::MAX);
+        self.accumulator = self.accumulator.saturating_add(scaled);
+        let steps =
+            u32::try_from(self.accumulator.as_nanos() / self.step.as_nanos()).unwrap_or(u32::MAX);
+        let consumed = self.step.checked_mul(steps).unwrap_or(self.accumulator);
+        self.accumulator -= consumed;
+        self.time = self.time.saturating_add(consumed);
+        steps
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::display::SteelSeriesLCDType;
+
+/// Runtime settings of a [`GameSenseAPI`](crate::GameSenseAPI)
+///
+/// All fields have sane defaults, so a config only needs to contain the settings which differ.
+/// As it implements serde's traits, it can be loaded from any format serde supports.
+///
+/// Every field can be overridden with an environment variable, which is applied when the API is
+/// created and takes precedence over the value in the config (see [`Config::apply_env`]):
+///
+/// | Variable                          | Field                    | Example           |
+/// |-----------------------------------|--------------------------|-------------------|
+/// | `SSSCREEN_DEVELOPER`              | `developer`              | `Max`             |
+/// | `SSSCREEN_GAME_DISPLAY_NAME`      | `game_display_name`      | `My Dashboard`    |
+/// | `SSSCREEN_ADDRESS`                | `address`                | `127.0.0.1:51234` |
+/// | `SSSCREEN_CORE_PROPS_PATH`        | `core_props_path`        | `/tmp/core.json`  |
+/// | `SSSCREEN_HEARTBEAT_INTERVAL_MS`  | `heartbeat_interval_ms`  | `5000`            |
+/// | `SSSCREEN_HEARTBEAT_JITTER_MS`    | `heartbeat_jitter_ms`    | `1000`            |
+/// | `SSSCREEN_TIMEOUT_MS`             | `timeout_ms`             | `1000`            |
+/// | `SSSCREEN_DISPLAYS`               | `displays`               | `Apex,Arctis`     |
+/// | `SSSCREEN_MAX_PAYLOAD_BYTES`      | `max_payload_bytes`      | `65536`           |
+/// | `SSSCREEN_SLOW_LATENCY_MS`        | `slow_latency_ms`        | `500`             |
+/// | `SSSCREEN_SLOW_FRAME_INTERVAL_MS` | `slow_frame_interval_ms` | `2000`            |
+/// | `SSSCREEN_DEINITIALIZE_TIMER_MS`  | `deinitialize_timer_ms`  | `30000`           |
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct Config {
+    /// Developer name shown in the SteelSeries GG Client
+    pub developer: Option<String>,
+    /// Human readable name of the game shown in the SteelSeries GG Client
+    pub game_display_name: Option<String>,
+    /// Address (`host:port`) of the GameSense API. Discovered from coreProps.json if unset.
+    pub address: Option<String>,
+    /// Path of the coreProps.json written by SteelSeries Engine. Uses the platform default if unset.
+    pub core_props_path: Option<PathBuf>,
+    /// Interval of the heartbeat in milliseconds. Kept at least a second below the deinitialize
+    /// timer of the Engine (15 seconds by default), so the screen is never reverted in between.
+    pub heartbeat_interval_ms: u64,
+    /// Up to how many milliseconds every heartbeat is sent earlier than the interval, at random.
+    /// Spreads the heartbeats of several apps instead of sending them all at once.
+    pub heartbeat_jitter_ms: u64,
+    /// Timeout of every request to the Engine in milliseconds
+    pub timeout_ms: u64,
+    /// The display types to manage
+    pub displays: Vec<SteelSeriesLCDType>,
+    /// Largest request body in bytes which is sent to the Engine, larger requests fail with
+    /// `Error::PayloadTooLarge`. Animations are split into several events to stay below it, see
+    /// `GameSenseAPI::bind_animation`.
+    pub max_payload_bytes: usize,
+    /// Smoothed request latency in milliseconds above which the Engine counts as slow and frames
+    /// are throttled, 0 disables throttling
+    pub slow_latency_ms: u64,
+    /// Minimum time in milliseconds between two frames while the Engine is slow, frames in
+    /// between are skipped
+    pub slow_frame_interval_ms: u64,
+    /// Time in milliseconds without events or heartbeats after which the Engine reverts the
+    /// screen (1000 to 60000). Uses the Engine default of 15 seconds if unset.
+    pub deinitialize_timer_ms: Option<u64>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            developer: None,
+            game_display_name: None,
+            address: None,
+            core_props_path: None,
+            heartbeat_interval_ms: 10_000,
+            heartbeat_jitter_ms: 0,
+            timeout_ms: 5_000,
+            displays: SteelSeriesLCDType::all().to_vec(),
+            max_payload_bytes: 256 * 1024,
+            slow_latency_ms: 500,
+            slow_frame_interval_ms: 1_000,
+            deinitialize_timer_ms: None,
+        }
+    }
+}
+
+impl Config {
+    /// Override fields with the `SSSCREEN_*` environment variables which are set. Invalid values
+    /// are ignored with a warning.
+    ///
+    /// Variables take precedence over every other source, including fields set in code, so an
+    /// installed app can be pointed to another Engine or tuned without rebuilding it. Fields
+    /// without a variable keep their value.
+    pub fn apply_env(&mut self) {
+        self.apply_vars(&|name| std::env::var(name).ok());
+    }
+
+    // Helper which overrides fields with the values `var` returns for the variable names
+    fn apply_vars(&mut self, var: &dyn Fn(&str) -> Option<String>) {
+        if let Some(developer) = env(var, "DEVELOPER") {
+            self.developer = Some(developer);
+        }
+        if let Some(name) = env(var, "GAME_DISPLAY_NAME") {
+            self.game_display_name = Some(name);
+        }
+        if let Some(address) = env(var, "ADDRESS") {
+            self.address = Some(address);
+        }
+        if let Some(path) = env(var, "CORE_PROPS_PATH") {
+            self.core_props_path = Some(PathBuf::from(path));
+        }
+        if let Some(interval) = parsed_env(var, "HEARTBEAT_INTERVAL_MS") {
+            self.heartbeat_interval_ms = interval;
+        }
+        if let Some(jitter) = parsed_env(var, "HEARTBEAT_JITTER_MS") {
+            self.heartbeat_jitter_ms = jitter;
+        }
+        if let Some(timeout) = parsed_env(var, "TIMEOUT_MS") {
+            self.timeout_ms = timeout;
+        }
+        if let Some(max) = parsed_env(var, "MAX_PAYLOAD_BYTES") {
+            self.max_payload_bytes = max;
+        }
+        if let Some(latency) = parsed_env(var, "SLOW_LATENCY_MS") {
+            self.slow_latency_ms = latency;
+        }
+        if let Some(interval) = parsed_env(var, "SLOW_FRAME_INTERVAL_MS") {
+            self.slow_frame_interval_ms = interval;
+        }
+        if let Some(timer) = parsed_env(var, "DEINITIALIZE_TIMER_MS") {
+            self.deinitialize_timer_ms = Some(timer);
+        }
+        if let Some(displays) = env(var, "DISPLAYS") {
+            let parsed: Result<Vec<SteelSeriesLCDType>, _> = displays
+                .split(',')
+                .map(|name| serde_json::from_value(name.trim().into()))
+                .collect();
+            match parsed {
+                Ok(displays) => self.displays = displays,
+                Err(_) => {
+                    println!("Warning: Ignoring invalid value of SSSCREEN_DISPLAYS: {displays}");
+                }
+            }
+        }
+    }
+
+    // The time until the next heartbeat: the interval within the bounds of the deinitialize
+    // timer, shortened by a random part of the jitter
+    pub(crate) fn heartbeat_delay(&self) -> Duration {
+        self.heartbeat_bounded()
+            .saturating_sub(self.heartbeat_jitter())
+            .max(Duration::from_millis(500))
+    }
+
+    // The period of a fixed heartbeat timer which is delayed by `heartbeat_jitter()` on every
+    // tick, so the time between two heartbeats never exceeds the bounded interval
+    #[cfg(feature = "async")]
+    pub(crate) fn heartbeat_period(&self) -> Duration {
+        self.heartbeat_bounded()
+            .saturating_sub(Duration::from_millis(self.heartbeat_jitter_ms))
+            .max(Duration::from_millis(500))
+    }
+
+    // A random part of the jitter
+    pub(crate) fn heartbeat_jitter(&self) -> Duration {
+        if self.heartbeat_jitter_ms == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_millis(
+            RandomState::new().build_hasher().finish() % (self.heartbeat_jitter_ms + 1),
+        )
+    }
+
+    // Helper which keeps the interval a second below the deinitialize timer
+    fn heartbeat_bounded(&self) -> Duration {
+        let timer = self
+            .deinitialize_timer_ms
+            .unwrap_or(15_000)
+            .clamp(1_000, 60_000);
+        Duration::from_millis(
+            self.heartbeat_interval_ms
+                .min(timer.saturating_sub(1_000))
+                .max(500),
+        )
+    }
+}
+
+// Helper which reads the variable `SSSCREEN_{name}`
+fn env(var: &dyn Fn(&str) -> Option<String>, name: &str) -> Option<String> {
+    var(&format!("SSSCREEN_{name}"))
+}
+
+// Helper which reads and parses the variable `SSSCREEN_{name}`
+fn parsed_env<T: FromStr>(var: &dyn Fn(&str) -> Option<String>, name: &str) -> Option<T> {
+    let value = env(var, name)?;
+    let parsed = value.parse().ok();
+    if parsed.is_none() {
+        println!("Warning: Ignoring invalid value of SSSCREEN_{name}: {value}");
+    }
+    parsed
+}
+
+#[cfg(feature = "toml")]
+impl Config {
+    /// Parse a config from TOML
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the TOML is malformed or contains invalid values.
+    pub fn from_toml(toml: &str) -> Result<Config, toml::de::Error> {
+        toml::from_str(toml)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    // Helper which applies the given variables to `config`
+    fn applied(mut config: Config, vars: &[(&str, &str)]) -> Config {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (format!("SSSCREEN_{name}"), (*value).to_string()))
+            .collect();
+        config.apply_vars(&|name| vars.get(name).cloned());
+        config
+    }
+
+    #[test]
+    fn variables_are_parsed() {
+        let config = applied(
+            Config::default(),
+            &[
+                ("DEVELOPER", "Max"),
+                ("GAME_DISPLAY_NAME", "My Dashboard"),
+                ("ADDRESS", "127.0.0.1:51234"),
+                ("CORE_PROPS_PATH", "/tmp/core.json"),
+                ("HEARTBEAT_INTERVAL_MS", "5000"),
+                ("HEARTBEAT_JITTER_MS", "1000"),
+                ("TIMEOUT_MS", "1000"),
+                ("DISPLAYS", "Apex, Arctis"),
+                ("MAX_PAYLOAD_BYTES", "65536"),
+                ("SLOW_LATENCY_MS", "250"),
+                ("SLOW_FRAME_INTERVAL_MS", "2000"),
+                ("DEINITIALIZE_TIMER_MS", "30000"),
+            ],
+        );
+        assert_eq!(
+            config,
+            Config {
+                developer: Some("Max".to_string()),
+                game_display_name: Some("My Dashboard".to_string()),
+                address: Some("127.0.0.1:51234".to_string()),
+                core_props_path: Some(PathBuf::from("/tmp/core.json")),
+                heartbeat_interval_ms: 5_000,
+                heartbeat_jitter_ms: 1_000,
+                timeout_ms: 1_000,
+                displays: vec![SteelSeriesLCDType::Apex, SteelSeriesLCDType::Arctis],
+                max_payload_bytes: 65_536,
+                slow_latency_ms: 250,
+                slow_frame_interval_ms: 2_000,
+                deinitialize_timer_ms: Some(30_000),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_ignored() {
+        for (name, value) in [
+            ("HEARTBEAT_INTERVAL_MS", "10s"),
+            ("HEARTBEAT_JITTER_MS", "-1"),
+            ("TIMEOUT_MS", ""),
+            ("MAX_PAYLOAD_BYTES", "64 KiB"),
+            ("SLOW_LATENCY_MS", "1.5"),
+            ("SLOW_FRAME_INTERVAL_MS", "18446744073709551616"),
+            ("DEINITIALIZE_TIMER_MS", "off"),
+            ("DISPLAYS", "Apex,Keyboard"),
+            ("DISPLAYS", ""),
+        ] {
+            assert_eq!(
+                applied(Config::default(), &[(name, value)]),
+                Config::default(),
+                "SSSCREEN_{name}={value}"
+            );
+        }
+    }
+
+    #[test]
+    fn variables_take_precedence_over_the_config() {
+        let config = Config {
+            developer: Some("Code".to_string()),
+            timeout_ms: 2_000,
+            displays: vec![SteelSeriesLCDType::Rival7x0],
+            ..Config::default()
+        };
+        let applied = applied(
+            config.clone(),
+            &[("DEVELOPER", "Env"), ("TIMEOUT_MS", "3000")],
+        );
+        assert_eq!(applied.developer.as_deref(), Some("Env"));
+        assert_eq!(applied.timeout_ms, 3_000);
+        // fields without a variable keep the value of the config
+        assert_eq!(applied.displays, config.displays);
+    }
+}
@@ -1,9 +1,10 @@
 use std::io::Error;
 
 use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+use serde::{Deserialize, Serialize};
 
 /// SteelSeries-Devices which can be targeted
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum SteelSeriesLCDType {
     /// Rival 700 and Rival 710
     Rival7x0,
@@ -17,6 +18,7 @@ pub enum SteelSeriesLCDType {
 
 impl SteelSeriesLCDType {
     /// Helper to get all supported LCD Types
+    #[must_use]
     pub fn all() -> &'static [SteelSeriesLCDType] {
         &[
             SteelSeriesLCDType::Apex,
@@ -27,6 +29,7 @@ impl SteelSeriesLCDType {
     }
 
     /// returns the dimensions for each type of hardware
+    #[must_use]
     pub fn dimensions(&self) -> Size {
         match self {
             Self::Apex => Size::new(128, 40),
@@ -38,17 +41,19 @@ impl SteelSeriesLCDType {
 }
 
 /// Display driver for SteelSeries devices
+#[derive(Clone)]
 pub struct SteelSeriesDisplay {
     lcd_type: SteelSeriesLCDType,
     pub framebuffer: Vec<u8>,
 }
 
 impl SteelSeriesDisplay {
-    /// Create a new instance of a display which can be used by embedded_graphics
+    /// Create a new instance of a display which can be used by `embedded_graphics`
     /// # Arguments
     ///
     /// * `lcd_type` - The device type which will be targeted
     ///
+    #[must_use]
     pub fn new(lcd_type: SteelSeriesLCDType) -> SteelSeriesDisplay {
         let size = lcd_type.dimensions();
 
@@ -59,6 +64,28 @@ impl SteelSeriesDisplay {
             framebuffer,
         }
     }
+
+    /// The device type this display targets
+    #[must_use]
+    pub fn lcd_type(&self) -> SteelSeriesLCDType {
+        self.lcd_type
+    }
+
+    /// Read back the color of a pixel, `None` if the point is outside of the display
+    #[must_use]
+    pub fn pixel(&self, point: Point) -> Option<BinaryColor> {
+        let size = self.size();
+        let x = usize::try_from(point.x).ok()?;
+        let y = usize::try_from(point.y).ok()?;
+        if x >= size.width as usize || y >= size.height as usize {
+            return None;
+        }
+        let pixel_index = y * 128 + x;
+        let bit_offset = 7 - (pixel_index % 8); // MSB-first in each byte
+        Some(BinaryColor::from(
+            self.framebuffer[pixel_index / 8] & (1 << bit_offset) != 0,
+        ))
+    }
 }
 
 impl OriginDimensions for SteelSeriesDisplay {
@@ -86,10 +113,11 @@ impl DrawTarget for SteelSeriesDisplay {
         let size = self.size();
         let width = i32::try_from(size.width).expect("Could not parse width!");
         let height = i32::try_from(size.height).expect("Could not parse height!");
-        // fill the framebuffer
+        // fill the framebuffer, pixels outside of the display are clipped silently as the same
+        // content is often drawn onto displays of different heights
         for Pixel(coord, color) in pixels {
             let (x, y) = coord.into();
-            if x >= 0 && y >= 0 && x < width && y <= height {
+            if x >= 0 && y >= 0 && x < width && y < height {
                 let pixel_index: usize = usize::try_from(y).expect("Could not parse y-coord") * 128
                     + usize::try_from(x).expect("Could not parse x-coord!");
                 let byte_index: usize = pixel_index / 8;
@@ -102,8 +130,6 @@ impl DrawTarget for SteelSeriesDisplay {
                         self.framebuffer[byte_index] &= !(1 << bit_offset);
                     }
                 }
-            } else {
-                println!("Warning: Ignoring attempt to draw pixel out of bounds! x: {x}, y: {y}");
             }
         }
         Ok(())
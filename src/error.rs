@@ -0,0 +1,89 @@
+use std::{fmt, path::PathBuf};
+
+/// Errors which can occur while talking to SteelSeries Engine
+#[derive(Debug)]
+pub enum Error {
+    /// The Engine information (coreProps.json) could not be found or parsed. This usually means
+    /// that SteelSeries GG is not installed or not running.
+    EngineNotFound {
+        /// Location where the Engine information was expected
+        path: PathBuf,
+    },
+    /// Reading the Engine information failed
+    Io(std::io::Error),
+    /// The request could not be sent or no response was received
+    Http(reqwest::Error),
+    /// The Engine responded with an error status
+    EngineRejected {
+        /// HTTP status code of the response
+        status: u16,
+        /// Error message sent by the Engine
+        message: String,
+    },
+    /// The request is larger than `Config::max_payload_bytes`
+    PayloadTooLarge {
+        /// Size of the serialized request in bytes
+        size: usize,
+        /// The configured maximum
+        limit: usize,
+    },
+    /// A handler does not match the schema of the GameSense SDK, detected before binding it
+    InvalidHandler {
+        /// Position of the handler in the list of bound handlers
+        index: usize,
+        /// What is wrong with the handler
+        message: String,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::EngineNotFound { path } => write!(
+                f,
+                "SteelSeries Engine information not found at {}. Is SteelSeries GG running?",
+                path.display()
+            ),
+            Error::Io(err) => write!(f, "Could not read SteelSeries Engine information: {err}"),
+            Error::Http(err) => write!(f, "Request to SteelSeries Engine failed: {err}"),
+            Error::EngineRejected { status, message } => {
+                write!(
+                    f,
+                    "SteelSeries Engine rejected the request ({status}): {message}"
+                )
+            }
+            Error::PayloadTooLarge { size, limit } => write!(
+                f,
+                "Request of {size} bytes exceeds the maximum payload size of {limit} bytes"
+            ),
+            Error::InvalidHandler { index, message } => {
+                write!(f, "Handler {index} is invalid: {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Http(err) => Some(err),
+            Error::EngineNotFound { .. }
+            | Error::EngineRejected { .. }
+            | Error::PayloadTooLarge { .. }
+            | Error::InvalidHandler { .. } => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Http(err)
+    }
+}
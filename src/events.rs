@@ -0,0 +1,152 @@
+//! Typed registration of GameSense events
+//!
+//! Registering an event with [`GameSenseAPI::register_game_event`](crate::GameSenseAPI::register_game_event)
+//! is optional, but lets the event show up in the SteelSeries GG UI with a proper icon and value
+//! range, so users can customize it.
+
+use serde_json::json;
+
+/// Built-in GameSense icons, used for events and text screens
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum Icon {
+    /// No icon
+    None,
+    Health,
+    Armor,
+    Ammo,
+    Money,
+    Flashbang,
+    Kills,
+    Headshot,
+    Helmet,
+    Hunger,
+    Air,
+    Compass,
+    Tool,
+    Mana,
+    Clock,
+    Lightning,
+    Item,
+    At,
+    Muted,
+    Talking,
+    Connect,
+    Disconnect,
+    Music,
+    Play,
+    Pause,
+    Cpu,
+    Gpu,
+    Ram,
+    Timer,
+    Temperature,
+    /// Any other icon id of the GameSense SDK
+    Other(u32),
+}
+
+impl Icon {
+    /// The GameSense icon id
+    #[must_use]
+    pub fn id(self) -> u32 {
+        match self {
+            Icon::None => 0,
+            Icon::Health => 1,
+            Icon::Armor => 2,
+            Icon::Ammo => 3,
+            Icon::Money => 4,
+            Icon::Flashbang => 5,
+            Icon::Kills => 6,
+            Icon::Headshot => 7,
+            Icon::Helmet => 8,
+            Icon::Hunger => 10,
+            Icon::Air => 11,
+            Icon::Compass => 12,
+            Icon::Tool => 13,
+            Icon::Mana => 14,
+            Icon::Clock => 15,
+            Icon::Lightning => 16,
+            Icon::Item => 17,
+            Icon::At => 18,
+            Icon::Muted => 19,
+            Icon::Talking => 20,
+            Icon::Connect => 21,
+            Icon::Disconnect => 22,
+            Icon::Music => 23,
+            Icon::Play => 24,
+            Icon::Pause => 25,
+            Icon::Cpu => 27,
+            Icon::Gpu => 28,
+            Icon::Ram => 29,
+            Icon::Timer => 42,
+            Icon::Temperature => 43,
+            Icon::Other(id) => id,
+        }
+    }
+}
+
+/// Metadata of a custom event
+///
+/// ```ignore
+/// api.register_game_event(&EventBuilder::new("HEALTH").icon(Icon::Health).range(0, 100))?;
+/// ```
+pub struct EventBuilder {
+    name: String,
+    icon: Icon,
+    range: Option<(i64, i64)>,
+    value_optional: bool,
+}
+
+impl EventBuilder {
+    /// Describe the event `name`. Allowed are upper-case A-Z, 0-9, hyphen, and underscore.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> EventBuilder {
+        EventBuilder {
+            name: name.into(),
+            icon: Icon::None,
+            range: None,
+            value_optional: true,
+        }
+    }
+
+    /// Icon shown for the event in SteelSeries GG
+    #[must_use]
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    /// Range of the values sent with the event (the Engine defaults to 0..=100)
+    #[must_use]
+    pub fn range(mut self, min: i64, max: i64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    /// Whether the handlers of the event work without a value (defaults to true). Set it to
+    /// false for events which drive value-based handlers like percent bars.
+    #[must_use]
+    pub fn value_optional(mut self, value_optional: bool) -> Self {
+        self.value_optional = value_optional;
+        self
+    }
+
+    /// The name of the event
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub(crate) fn to_json(&self, game: &str) -> serde_json::Value {
+        let mut body = json!({
+            "game": game,
+            "event": self.name,
+            "icon_id": self.icon.id(),
+            "value_optional": self.value_optional,
+        });
+        if let Some((min, max)) = self.range {
+            body["min_value"] = min.into();
+            body["max_value"] = max.into();
+        }
+        body
+    }
+}
@@ -0,0 +1,28 @@
+//! Compile-time checks of the feature set
+//!
+//! Embedders depend on the crate with `default-features = false` and expect nothing but the
+//! GameSense client, the text helpers and the widgets to be compiled, while `full` is meant to
+//! enable everything. Every optional module is gated on its feature in lib.rs.
+
+// `full` has to enable every optional subsystem
+const _: () = assert!(
+    !cfg!(feature = "full")
+        || (cfg!(feature = "async")
+            && cfg!(feature = "capture")
+            && cfg!(feature = "cjk-font")
+            && cfg!(feature = "clipboard")
+            && cfg!(feature = "font-pack")
+            && cfg!(feature = "idle")
+            && cfg!(feature = "intl-font")
+            && cfg!(feature = "latency")
+            && cfg!(feature = "livesplit")
+            && cfg!(feature = "mock")
+            && cfg!(feature = "recording")
+            && cfg!(feature = "secrets")
+            && cfg!(feature = "slideshow")
+            && cfg!(feature = "telemetry")
+            && cfg!(feature = "toml")
+            && cfg!(feature = "totp")
+            && cfg!(feature = "world-clock")),
+    "the `full` feature must enable every optional subsystem"
+);
@@ -0,0 +1,165 @@
+//! Built-in pixel fonts with broad European coverage
+//!
+//! The mono fonts of embedded-graphics are split by ISO 8859 charset, so each of them only covers
+//! a single script. The fonts in this module combine the Latin-1, Latin-2, Cyrillic and Greek
+//! variants of the same glyph size and pick the matching variant for every character, which allows
+//! rendering e.g. Cyrillic track titles next to Latin text without any external font files.
+//!
+//! Only available with the `intl-font` feature.
+
+use embedded_graphics::{
+    mono_font::{MonoFont, MonoTextStyle, iso_8859_1, iso_8859_2, iso_8859_5, iso_8859_7},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{
+        Baseline,
+        renderer::{TextMetrics, TextRenderer},
+    },
+};
+
+/// A set of mono fonts sharing the same glyph size, each covering a different script
+pub struct EuropeanFont {
+    latin: &'static MonoFont<'static>,
+    central: &'static MonoFont<'static>,
+    cyrillic: &'static MonoFont<'static>,
+    greek: &'static MonoFont<'static>,
+}
+
+/// 5x8 pixel font
+pub const FONT_5X8: EuropeanFont = EuropeanFont {
+    latin: &iso_8859_1::FONT_5X8,
+    central: &iso_8859_2::FONT_5X8,
+    cyrillic: &iso_8859_5::FONT_5X8,
+    greek: &iso_8859_7::FONT_5X8,
+};
+
+/// 6x10 pixel font
+pub const FONT_6X10: EuropeanFont = EuropeanFont {
+    latin: &iso_8859_1::FONT_6X10,
+    central: &iso_8859_2::FONT_6X10,
+    cyrillic: &iso_8859_5::FONT_6X10,
+    greek: &iso_8859_7::FONT_6X10,
+};
+
+/// 7x13 pixel font
+pub const FONT_7X13: EuropeanFont = EuropeanFont {
+    latin: &iso_8859_1::FONT_7X13,
+    central: &iso_8859_2::FONT_7X13,
+    cyrillic: &iso_8859_5::FONT_7X13,
+    greek: &iso_8859_7::FONT_7X13,
+};
+
+#[derive(PartialEq, Eq, Clone, Copy)]
+enum Script {
+    Latin,
+    Central,
+    Cyrillic,
+    Greek,
+}
+
+impl Script {
+    fn of(c: char) -> Script {
+        match c {
+            '\u{0100}'..='\u{017F}' => Script::Central,
+            '\u{0370}'..='\u{03FF}' => Script::Greek,
+            '\u{0400}'..='\u{04FF}' => Script::Cyrillic,
+            _ => Script::Latin,
+        }
+    }
+}
+
+impl EuropeanFont {
+    fn font(&self, script: Script) -> &'static MonoFont<'static> {
+        match script {
+            Script::Latin => self.latin,
+            Script::Central => self.central,
+            Script::Cyrillic => self.cyrillic,
+            Script::Greek => self.greek,
+        }
+    }
+}
+
+/// Text style for an [`EuropeanFont`], usable with `embedded_graphics::text::Text`
+#[derive(Clone, Copy)]
+pub struct EuropeanTextStyle {
+    font: &'static EuropeanFont,
+    color: BinaryColor,
+}
+
+impl EuropeanTextStyle {
+    /// Create a new text style
+    /// # Arguments
+    ///
+    /// * `font` - The font to use, e.g. [`FONT_6X10`]
+    /// * `color` - The color of the text
+    ///
+    #[must_use]
+    pub fn new(font: &'static EuropeanFont, color: BinaryColor) -> EuropeanTextStyle {
+        EuropeanTextStyle { font, color }
+    }
+
+    fn style(&self, script: Script) -> MonoTextStyle<'static, BinaryColor> {
+        MonoTextStyle::new(self.font.font(script), self.color)
+    }
+}
+
+impl TextRenderer for EuropeanTextStyle {
+    type Color = BinaryColor;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        // draw the text in runs of characters which share the same script
+        let mut position = position;
+        let mut run_start = 0;
+        let mut run_script = Script::Latin;
+        for (index, c) in text.char_indices() {
+            let script = Script::of(c);
+            if script != run_script {
+                if index > run_start {
+                    position = self.style(run_script).draw_string(
+                        &text[run_start..index],
+                        position,
+                        baseline,
+                        target,
+                    )?;
+                }
+                run_start = index;
+                run_script = script;
+            }
+        }
+        self.style(run_script)
+            .draw_string(&text[run_start..], position, baseline, target)
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        self.style(Script::Latin)
+            .draw_whitespace(width, position, baseline, target)
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        // all variants share the same glyph size, so measuring with any of them is exact
+        self.style(Script::Latin)
+            .measure_string(text, position, baseline)
+    }
+
+    fn line_height(&self) -> u32 {
+        self.style(Script::Latin).line_height()
+    }
+}
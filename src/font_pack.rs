@@ -0,0 +1,743 @@
+//! Build-time packing of TrueType fonts into embedded bitmap fonts
+//!
+//! The mono fonts of embedded-graphics are crisp, but only come in a few plain designs.
+//! [`FontPacker`] rasterizes the glyphs of a TrueType font at a chosen pixel size and emits them as
+//! Rust source defining an embedded-graphics `MonoFont`, which gives the look of a real font without
+//! any font dependency at runtime. Only the requested characters are packed, so the font stays
+//! small.
+//!
+//! Run it from the build script of the app, with this crate as a build dependency:
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("clock_font.rs");
+//!     FontPacker::load("fonts/Inter.ttf")
+//!         .unwrap()
+//!         .pixel_size(20)
+//!         .chars("0123456789:")
+//!         .pack()
+//!         .unwrap()
+//!         .write_rust(out, "CLOCK_FONT")
+//!         .unwrap();
+//!     println!("cargo::rerun-if-changed=fonts/Inter.ttf");
+//! }
+//!
+//! // main.rs
+//! include!(concat!(env!("OUT_DIR"), "/clock_font.rs"));
+//! let style = MonoTextStyle::new(&CLOCK_FONT, BinaryColor::On);
+//! ```
+//!
+//! Every glyph is centered in a cell as wide as the widest packed character. Only fonts with
+//! TrueType outlines are supported, not OpenType fonts with CFF outlines.
+//!
+//! Only available with the `font-pack` feature.
+
+use std::{
+    fmt::Write,
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+use embedded_graphics::prelude::Size;
+
+// supersampling of every pixel in both directions, a pixel is set if half of its samples are
+const SAMPLES: usize = 4;
+// line segments per quadratic curve of an outline
+const CURVE_SEGMENTS: usize = 8;
+// nesting limit of composite glyphs, to stop at malicious fonts
+const MAX_DEPTH: u8 = 8;
+const GLYPHS_PER_ROW: usize = 16;
+
+/// Rasterizes the glyphs of a TrueType font into a [`PackedFont`]
+pub struct FontPacker {
+    data: Vec<u8>,
+    pixel_size: u32,
+    chars: Vec<char>,
+}
+
+impl FontPacker {
+    /// Create a packer for a font in the TrueType format (`.ttf`)
+    #[must_use]
+    pub fn new(data: Vec<u8>) -> FontPacker {
+        FontPacker {
+            data,
+            pixel_size: 12,
+            chars: (' '..='~').collect(),
+        }
+    }
+
+    /// Create a packer for the font at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<FontPacker, Error> {
+        Ok(FontPacker::new(std::fs::read(path)?))
+    }
+
+    /// Set the size of the em square in pixels (defaults to 12). The line height is a bit larger,
+    /// as it includes the ascenders and descenders of the font.
+    #[must_use]
+    pub fn pixel_size(mut self, pixel_size: u32) -> Self {
+        self.pixel_size = pixel_size.max(1);
+        self
+    }
+
+    /// Set the characters to pack (defaults to printable ASCII). Control characters are ignored.
+    #[must_use]
+    pub fn chars(mut self, chars: &str) -> Self {
+        self.chars = chars.chars().filter(|c| !c.is_control()).collect();
+        self.chars.sort_unstable();
+        self.chars.dedup();
+        self
+    }
+
+    /// Rasterize the characters. Characters which the font doesn't contain are skipped with a
+    /// warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `InvalidData` if the font could not be parsed or contains none of
+    /// the characters.
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn pack(&self) -> Result<PackedFont, Error> {
+        let font = TrueType::parse(&self.data).ok_or_else(|| invalid("unsupported font file"))?;
+        let scale = self.pixel_size as f32 / f32::from(font.units_per_em);
+        let mut glyphs = vec![];
+        for &c in &self.chars {
+            match font.glyph_index(c) {
+                Some(glyph) => glyphs.push((c, glyph)),
+                None => println!("Warning: The font has no glyph for {c:?}, skipping it"),
+            }
+        }
+        if glyphs.is_empty() {
+            return Err(invalid("the font contains none of the characters"));
+        }
+
+        let ascender = f32::from(font.ascender) * scale;
+        let descender = f32::from(font.descender) * scale;
+        let height = ((ascender - descender).ceil() as u32).max(1);
+        let baseline = ascender.round();
+        let mut advances = vec![];
+        for (_, glyph) in &glyphs {
+            advances.push(f32::from(font.advance(*glyph).ok_or_else(broken)?) * scale);
+        }
+        let width = (advances
+            .iter()
+            .fold(1.0, |max: f32, advance| max.max(*advance))
+            .ceil()) as u32;
+
+        let per_row = glyphs.len().min(GLYPHS_PER_ROW);
+        let image_width = width as usize * per_row;
+        let row_bytes = image_width.div_ceil(8);
+        let rows = glyphs.len().div_ceil(per_row);
+        let mut image = vec![0; row_bytes * rows * height as usize];
+        for (index, ((_, glyph), advance)) in glyphs.iter().zip(&advances).enumerate() {
+            let offset = (width as f32 - advance) / 2.0;
+            let edges: Vec<Edge> = font
+                .outline(*glyph, MAX_DEPTH)
+                .ok_or_else(broken)?
+                .iter()
+                .flat_map(|contour| flatten(contour))
+                .map(|((x0, y0), (x1, y1))| {
+                    (
+                        (x0 * scale + offset, baseline - y0 * scale),
+                        (x1 * scale + offset, baseline - y1 * scale),
+                    )
+                })
+                .collect();
+            let left = index % per_row * width as usize;
+            let top = index / per_row * height as usize;
+            for (x, y) in rasterize(&edges, width as usize, height as usize) {
+                let (x, y) = (left + x, top + y);
+                image[y * row_bytes + x / 8] |= 0x80 >> (x % 8);
+            }
+        }
+
+        let chars: String = glyphs.iter().map(|(c, _)| *c).collect();
+        Ok(PackedFont {
+            replacement: chars.chars().position(|c| c == '?').unwrap_or(0),
+            chars,
+            character_size: Size::new(width, height),
+            baseline: baseline.max(0.0) as u32,
+            image_width: u32::try_from(image_width).unwrap_or(u32::MAX),
+            image,
+        })
+    }
+}
+
+/// Glyphs of a font rasterized by [`FontPacker`], ready to be written as Rust source
+pub struct PackedFont {
+    chars: String,
+    replacement: usize,
+    character_size: Size,
+    baseline: u32,
+    image_width: u32,
+    image: Vec<u8>,
+}
+
+impl PackedFont {
+    /// The packed characters
+    #[must_use]
+    pub fn chars(&self) -> &str {
+        &self.chars
+    }
+
+    /// Size of every glyph cell in pixels
+    #[must_use]
+    pub fn character_size(&self) -> Size {
+        self.character_size
+    }
+
+    /// Rust source defining the font as a `MonoFont` constant named `name`. Characters which were
+    /// not packed are shown as `?`, or the first character if `?` wasn't packed.
+    #[must_use]
+    pub fn to_rust(&self, name: &str) -> String {
+        let mut bytes = String::new();
+        for (index, byte) in self.image.iter().enumerate() {
+            let separator = if index % 16 == 0 {
+                "\n            "
+            } else {
+                " "
+            };
+            let _ = write!(bytes, "{separator}0x{byte:02X},");
+        }
+        let Size { width, height } = self.character_size;
+        format!(
+            "// Generated by steelseries_screen::font_pack, do not edit
+pub const {name}: embedded_graphics::mono_font::MonoFont<'static> = embedded_graphics::mono_font::MonoFont {{
+    image: embedded_graphics::image::ImageRaw::new(
+        &[{bytes}
+        ],
+        {image_width},
+    ),
+    glyph_mapping: &embedded_graphics::mono_font::mapping::StrGlyphMapping::new({chars:?}, {replacement}),
+    character_size: embedded_graphics::geometry::Size::new({width}, {height}),
+    character_spacing: 0,
+    baseline: {baseline},
+    underline: embedded_graphics::mono_font::DecorationDimensions::new({underline}, 1),
+    strikethrough: embedded_graphics::mono_font::DecorationDimensions::default_strikethrough({height}),
+}};
+",
+            image_width = self.image_width,
+            chars = self.chars,
+            replacement = self.replacement,
+            baseline = self.baseline,
+            underline = (self.baseline + 1).min(height.saturating_sub(1)),
+        )
+    }
+
+    /// Write the Rust source of [`PackedFont::to_rust`] to a file, e.g. in the `OUT_DIR` of a build
+    /// script
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be written.
+    pub fn write_rust<P: AsRef<Path>>(&self, path: P, name: &str) -> Result<(), Error> {
+        std::fs::write(path, self.to_rust(name))
+    }
+}
+
+type Point = (f32, f32);
+type Edge = (Point, Point);
+
+// The tables of a TrueType font needed to rasterize glyphs
+struct TrueType<'a> {
+    data: &'a [u8],
+    units_per_em: u16,
+    ascender: i16,
+    descender: i16,
+    long_offsets: bool,
+    glyph_count: u16,
+    metrics_count: u16,
+    hmtx: usize,
+    loca: usize,
+    glyf: usize,
+    cmap: usize,
+}
+
+impl<'a> TrueType<'a> {
+    fn parse(data: &'a [u8]) -> Option<TrueType<'a>> {
+        let head = table(data, *b"head")?;
+        let hhea = table(data, *b"hhea")?;
+        Some(TrueType {
+            data,
+            units_per_em: u16_at(data, head + 18).filter(|units| *units > 0)?,
+            ascender: i16_at(data, hhea + 4)?,
+            descender: i16_at(data, hhea + 6)?,
+            long_offsets: i16_at(data, head + 50)? == 1,
+            glyph_count: u16_at(data, table(data, *b"maxp")? + 4)?,
+            metrics_count: u16_at(data, hhea + 34).filter(|count| *count > 0)?,
+            hmtx: table(data, *b"hmtx")?,
+            loca: table(data, *b"loca")?,
+            glyf: table(data, *b"glyf")?,
+            cmap: unicode_cmap(data, table(data, *b"cmap")?)?,
+        })
+    }
+
+    // index of the glyph of a character, `None` if the font has no glyph for it
+    fn glyph_index(&self, c: char) -> Option<u16> {
+        let data = self.data;
+        let cmap = self.cmap;
+        let code = u32::from(c);
+        let glyph = if u16_at(data, cmap)? == 12 {
+            let groups = u32_at(data, cmap + 12)? as usize;
+            (0..groups).find_map(|group| {
+                let group = cmap + 16 + group * 12;
+                let start = u32_at(data, group)?;
+                (start..=u32_at(data, group + 4)?)
+                    .contains(&code)
+                    .then(|| u32_at(data, group + 8))?
+                    .map(|first| first + code - start)
+            })?
+        } else {
+            let code = u16::try_from(code).ok()?;
+            let segments = usize::from(u16_at(data, cmap + 6)? / 2);
+            let ends = cmap + 14;
+            let starts = ends + 2 * segments + 2;
+            let deltas = starts + 2 * segments;
+            let ranges = deltas + 2 * segments;
+            let segment = (0..segments)
+                .find(|segment| u16_at(data, ends + 2 * segment).is_some_and(|end| end >= code))?;
+            let start = u16_at(data, starts + 2 * segment)?;
+            if code < start {
+                return None;
+            }
+            let delta = u16_at(data, deltas + 2 * segment)?;
+            let range = usize::from(u16_at(data, ranges + 2 * segment)?);
+            let glyph = if range == 0 {
+                code
+            } else {
+                let at = ranges + 2 * segment + range + 2 * usize::from(code - start);
+                u16_at(data, at).filter(|glyph| *glyph != 0)?
+            };
+            u32::from(glyph.wrapping_add(delta))
+        };
+        u16::try_from(glyph)
+            .ok()
+            .filter(|glyph| *glyph != 0 && *glyph < self.glyph_count)
+    }
+
+    // advance width of a glyph in font units
+    fn advance(&self, glyph: u16) -> Option<u16> {
+        let metric = glyph.min(self.metrics_count - 1);
+        u16_at(self.data, self.hmtx + 4 * usize::from(metric))
+    }
+
+    // the contours of a glyph in font units, with a flag for the points on the curve
+    fn outline(&self, glyph: u16, depth: u8) -> Option<Vec<Vec<(f32, f32, bool)>>> {
+        let data = self.data;
+        let offset = |glyph: usize| {
+            Some(if self.long_offsets {
+                u32_at(data, self.loca + 4 * glyph)? as usize
+            } else {
+                usize::from(u16_at(data, self.loca + 2 * glyph)?) * 2
+            })
+        };
+        let start = offset(usize::from(glyph))?;
+        if offset(usize::from(glyph) + 1)? <= start {
+            return Some(vec![]);
+        }
+        let glyph = self.glyf + start;
+        let contours = i16_at(data, glyph)?;
+        if contours >= 0 {
+            simple_outline(data, glyph, usize::try_from(contours).ok()?)
+        } else if depth > 0 {
+            self.composite_outline(glyph, depth - 1)
+        } else {
+            None
+        }
+    }
+
+    // Helper which combines the transformed outlines of the components of a composite glyph
+    fn composite_outline(&self, glyph: usize, depth: u8) -> Option<Vec<Vec<(f32, f32, bool)>>> {
+        const WORDS: u16 = 0x0001;
+        const XY_VALUES: u16 = 0x0002;
+        const SCALE: u16 = 0x0008;
+        const MORE: u16 = 0x0020;
+        const XY_SCALE: u16 = 0x0040;
+        const TWO_BY_TWO: u16 = 0x0080;
+
+        let data = self.data;
+        let scale = |at: usize| Some(f32::from(i16_at(data, at)?) / 16384.0);
+        let mut contours = vec![];
+        let mut at = glyph + 10;
+        loop {
+            let flags = u16_at(data, at)?;
+            let component = u16_at(data, at + 2)?;
+            at += 4;
+            let (dx, dy) = if flags & WORDS == 0 {
+                let byte = |at: usize| data.get(at).map(|byte| f32::from(byte.cast_signed()));
+                at += 2;
+                (byte(at - 2)?, byte(at - 1)?)
+            } else {
+                at += 4;
+                (
+                    f32::from(i16_at(data, at - 4)?),
+                    f32::from(i16_at(data, at - 2)?),
+                )
+            };
+            // components aligned by matching points are placed at the origin
+            let (dx, dy) = if flags & XY_VALUES == 0 {
+                (0.0, 0.0)
+            } else {
+                (dx, dy)
+            };
+            let [a, b, c, d] = if flags & SCALE != 0 {
+                at += 2;
+                let scale = scale(at - 2)?;
+                [scale, 0.0, 0.0, scale]
+            } else if flags & XY_SCALE != 0 {
+                at += 4;
+                [scale(at - 4)?, 0.0, 0.0, scale(at - 2)?]
+            } else if flags & TWO_BY_TWO != 0 {
+                at += 8;
+                [
+                    scale(at - 8)?,
+                    scale(at - 6)?,
+                    scale(at - 4)?,
+                    scale(at - 2)?,
+                ]
+            } else {
+                [1.0, 0.0, 0.0, 1.0]
+            };
+            for contour in self.outline(component, depth)? {
+                contours.push(
+                    contour
+                        .into_iter()
+                        .map(|(x, y, on)| (a * x + c * y + dx, b * x + d * y + dy, on))
+                        .collect(),
+                );
+            }
+            if flags & MORE == 0 {
+                return Some(contours);
+            }
+        }
+    }
+}
+
+// Helper which decodes the contours of a simple glyph
+fn simple_outline(
+    data: &[u8],
+    glyph: usize,
+    contours: usize,
+) -> Option<Vec<Vec<(f32, f32, bool)>>> {
+    const ON_CURVE: u8 = 0x01;
+    const X_SHORT: u8 = 0x02;
+    const Y_SHORT: u8 = 0x04;
+    const REPEAT: u8 = 0x08;
+    const X_SAME: u8 = 0x10;
+    const Y_SAME: u8 = 0x20;
+
+    let ends: Vec<usize> = (0..contours)
+        .map(|contour| u16_at(data, glyph + 10 + 2 * contour).map(usize::from))
+        .collect::<Option<_>>()?;
+    let points = ends.last().map_or(0, |end| end + 1);
+    let instructions = usize::from(u16_at(data, glyph + 10 + 2 * contours)?);
+    let mut at = glyph + 12 + 2 * contours + instructions;
+
+    let mut flags = Vec::with_capacity(points);
+    while flags.len() < points {
+        let flag = *data.get(at)?;
+        at += 1;
+        let mut count = 1;
+        if flag & REPEAT != 0 {
+            count += usize::from(*data.get(at)?);
+            at += 1;
+        }
+        flags.extend(std::iter::repeat_n(flag, count));
+    }
+    flags.truncate(points);
+
+    let mut coordinates = |short: u8, same: u8| {
+        let mut value = 0i32;
+        let mut values = Vec::with_capacity(points);
+        for flag in &flags {
+            if flag & short != 0 {
+                let delta = i32::from(*data.get(at)?);
+                at += 1;
+                value += if flag & same != 0 { delta } else { -delta };
+            } else if flag & same == 0 {
+                value += i32::from(i16_at(data, at)?);
+                at += 2;
+            }
+            values.push(value);
+        }
+        Some(values)
+    };
+    let xs = coordinates(X_SHORT, X_SAME)?;
+    let ys = coordinates(Y_SHORT, Y_SAME)?;
+
+    let mut start = 0;
+    let mut outline = vec![];
+    for end in ends {
+        #[allow(clippy::cast_precision_loss)]
+        let contour = (start..=end.min(points - 1))
+            .map(|point| {
+                (
+                    xs[point] as f32,
+                    ys[point] as f32,
+                    flags[point] & ON_CURVE != 0,
+                )
+            })
+            .collect();
+        outline.push(contour);
+        start = end + 1;
+    }
+    Some(outline)
+}
+
+// Helper which turns a closed contour of on- and off-curve points into line segments
+#[allow(clippy::cast_precision_loss)]
+fn flatten(contour: &[(f32, f32, bool)]) -> Vec<Edge> {
+    let Some(&(x, y, on)) = contour.first() else {
+        return vec![];
+    };
+    let midpoint = |a: Point, b: Point| (f32::midpoint(a.0, b.0), f32::midpoint(a.1, b.1));
+    // start at a point on the curve, between two off-curve points there is an implied one
+    let last = contour[contour.len() - 1];
+    let start = if on {
+        (x, y)
+    } else if last.2 {
+        (last.0, last.1)
+    } else {
+        midpoint((x, y), (last.0, last.1))
+    };
+
+    let mut edges = vec![];
+    let mut previous = start;
+    let mut control: Option<Point> = None;
+    let curve = |from: Point, control: Point, to: Point, edges: &mut Vec<Edge>| {
+        let mut from_point = from;
+        for step in 1..=CURVE_SEGMENTS {
+            let t = step as f32 / CURVE_SEGMENTS as f32;
+            let u = 1.0 - t;
+            let point = (
+                u * u * from.0 + 2.0 * u * t * control.0 + t * t * to.0,
+                u * u * from.1 + 2.0 * u * t * control.1 + t * t * to.1,
+            );
+            edges.push((from_point, point));
+            from_point = point;
+        }
+    };
+    for &(x, y, on) in contour
+        .iter()
+        .chain(std::iter::once(&(start.0, start.1, true)))
+    {
+        let point = (x, y);
+        match (on, control) {
+            (true, None) => edges.push((previous, point)),
+            (true, Some(control)) => curve(previous, control, point, &mut edges),
+            (false, None) => {
+                control = Some(point);
+                continue;
+            }
+            (false, Some(pending)) => {
+                let implied = midpoint(pending, point);
+                curve(previous, pending, implied, &mut edges);
+                previous = implied;
+                control = Some(point);
+                continue;
+            }
+        }
+        previous = point;
+        control = None;
+    }
+    edges
+}
+
+// Helper which returns the pixels of a `width`x`height` cell covered by the outline, using the
+// non-zero winding rule
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn rasterize(edges: &[Edge], width: usize, height: usize) -> Vec<(usize, usize)> {
+    let samples = SAMPLES as f32;
+    let mut coverage = vec![0; width * height];
+    let mut crossings = vec![];
+    for row in 0..height * SAMPLES {
+        let y = (row as f32 + 0.5) / samples;
+        crossings.clear();
+        for &((x0, y0), (x1, y1)) in edges {
+            if (y0 <= y && y < y1) || (y1 <= y && y < y0) {
+                let x = x0 + (y - y0) * (x1 - x0) / (y1 - y0);
+                crossings.push((x, if y1 > y0 { 1 } else { -1 }));
+            }
+        }
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            if winding == 0 {
+                continue;
+            }
+            let first = (pair[0].0 * samples - 0.5).ceil().max(0.0) as usize;
+            let end = ((pair[1].0 * samples - 0.5).ceil().max(0.0) as usize).min(width * SAMPLES);
+            for column in first..end {
+                coverage[row / SAMPLES * width + column / SAMPLES] += 1;
+            }
+        }
+    }
+    coverage
+        .iter()
+        .enumerate()
+        .filter(|(_, covered)| **covered * 2 >= SAMPLES * SAMPLES)
+        .map(|(index, _)| (index % width, index / width))
+        .collect()
+}
+
+// Helper which finds the offset of a table of the font
+fn table(data: &[u8], tag: [u8; 4]) -> Option<usize> {
+    let count = usize::from(u16_at(data, 4)?);
+    let record = (0..count)
+        .map(|index| 12 + 16 * index)
+        .find(|record| data.get(*record..record + 4) == Some(tag.as_slice()))?;
+    usize::try_from(u32_at(data, record + 8)?).ok()
+}
+
+// Helper which finds the offset of a Unicode character map (format 4 or 12) of the font,
+// preferring the full Unicode range of format 12
+fn unicode_cmap(data: &[u8], cmap: usize) -> Option<usize> {
+    let count = usize::from(u16_at(data, cmap + 2)?);
+    let mut maps = vec![];
+    for index in 0..count {
+        let record = cmap + 4 + 8 * index;
+        let platform = u16_at(data, record)?;
+        let encoding = u16_at(data, record + 2)?;
+        if platform == 0 || (platform == 3 && matches!(encoding, 1 | 10)) {
+            let offset = cmap + usize::try_from(u32_at(data, record + 4)?).ok()?;
+            maps.push((u16_at(data, offset)?, offset));
+        }
+    }
+    maps.iter()
+        .find(|(format, _)| *format == 12)
+        .or_else(|| maps.iter().find(|(format, _)| *format == 4))
+        .map(|(_, offset)| *offset)
+}
+
+// Helpers which read big-endian numbers of the font
+fn u16_at(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn i16_at(data: &[u8], at: usize) -> Option<i16> {
+    u16_at(data, at).map(u16::cast_signed)
+}
+
+fn u32_at(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+// Helper which creates the error for fonts which could not be parsed
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, format!("Invalid font: {message}"))
+}
+
+fn broken() -> Error {
+    invalid("broken glyph data")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Helper which builds a font with 1000 units per em, an ascender of 800 and a descender of
+    // -200, whose only glyph is a box for `A` from the baseline to the ascender, as wide as its
+    // advance of 1000 units
+    fn box_font() -> Vec<u8> {
+        let be16 = |values: &[i32]| -> Vec<u8> {
+            values
+                .iter()
+                .flat_map(|value| u16::try_from(value & 0xFFFF).unwrap().to_be_bytes())
+                .collect()
+        };
+        let mut head = vec![0; 54];
+        head[18..20].copy_from_slice(&1000u16.to_be_bytes());
+        head[50..52].copy_from_slice(&1u16.to_be_bytes());
+        let mut hhea = vec![0; 36];
+        hhea[4..8].copy_from_slice(&be16(&[800, -200]));
+        hhea[34..36].copy_from_slice(&2u16.to_be_bytes());
+        let maxp = be16(&[0, 0x5000, 2]);
+        let hmtx = be16(&[500, 0, 1000, 0]);
+        // one contour with 4 points on the curve, coordinates as deltas
+        let mut glyph = be16(&[1, 0, 0, 1000, 800, 3, 0]);
+        glyph.extend([0x01; 4]);
+        glyph.extend(be16(&[0, 1000, 0, -1000, 0, 0, 800, 0]));
+        let loca: Vec<u8> = [0, 0, glyph.len()]
+            .iter()
+            .flat_map(|offset| u32::try_from(*offset).unwrap().to_be_bytes())
+            .collect();
+        // format 4 mapping `A` to glyph 1, with the final segment for 0xFFFF
+        let mut cmap = be16(&[0, 1, 3, 1, 0, 12]);
+        cmap.extend(be16(&[4, 32, 0, 4, 0, 0, 0]));
+        cmap.extend(be16(&[65, 0xFFFF, 0, 65, 0xFFFF, 1 - 65, 1, 0, 0]));
+
+        let tables = [
+            (b"cmap", cmap),
+            (b"glyf", glyph),
+            (b"head", head),
+            (b"hhea", hhea),
+            (b"hmtx", hmtx),
+            (b"loca", loca),
+            (b"maxp", maxp),
+        ];
+        let mut font = be16(&[1, 0, 7, 0, 0, 0]);
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, table) in &tables {
+            font.extend(*tag);
+            font.extend([0; 4]);
+            for value in [offset, table.len()] {
+                font.extend(u32::try_from(value).unwrap().to_be_bytes());
+            }
+            offset += table.len();
+        }
+        for (_, table) in &tables {
+            font.extend(table);
+        }
+        font
+    }
+
+    #[test]
+    fn packs_the_requested_glyphs() {
+        let font = FontPacker::new(box_font())
+            .pixel_size(10)
+            .chars("AB\n")
+            .pack()
+            .unwrap();
+        // `B` is missing in the font
+        assert_eq!(font.chars(), "A");
+        assert_eq!(font.character_size(), Size::new(10, 10));
+        // the box covers the 8 rows above the baseline
+        let rows: Vec<u16> = font
+            .image
+            .chunks(2)
+            .map(|row| u16::from_be_bytes([row[0], row[1]]))
+            .collect();
+        assert_eq!(rows, [[0xFFC0; 8].as_slice(), &[0; 2]].concat());
+
+        let source = font.to_rust("BOX");
+        assert!(source.contains("pub const BOX: embedded_graphics::mono_font::MonoFont<'static>"));
+        assert!(source.contains("StrGlyphMapping::new(\"A\", 0)"));
+        assert!(source.contains("baseline: 8,"));
+    }
+
+    #[test]
+    fn rejects_unusable_fonts() {
+        for packer in [
+            FontPacker::new(b"not a font".to_vec()),
+            FontPacker::new(box_font()).chars("xyz"),
+        ] {
+            let Err(error) = packer.pack() else {
+                panic!("packed an unusable font");
+            };
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Frames as plain values
+//!
+//! [`Frame`] holds the pixels of a black and white image packed into bits, so frames can be
+//! stored, compared and combined without going through a `DrawTarget` every time:
+//!
+//! ```ignore
+//! let background = Frame::from(&display);
+//! let mut frame = &background ^ &cursor;
+//! frame.overlay(&icon, Point::new(100, 4), Overlay::Or);
+//! frame.render(&mut display);
+//! ```
+
+use std::{
+    convert::Infallible,
+    ops::{BitAnd, BitOr, BitXor, Not},
+};
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+use crate::{display::SteelSeriesDisplay, frame_source::FrameSource};
+
+/// How the pixels of an overlay are combined with the pixels below
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Overlay {
+    /// The overlay replaces the pixels below
+    #[default]
+    Replace,
+    /// Pixels are on where either frame is on
+    Or,
+    /// Pixels are on where both frames are on
+    And,
+    /// Pixels of the overlay invert the pixels below
+    Xor,
+}
+
+impl Overlay {
+    // Helper which combines a pixel below with a pixel of the overlay
+    fn combine(self, below: bool, above: bool) -> bool {
+        match self {
+            Overlay::Replace => above,
+            Overlay::Or => below | above,
+            Overlay::And => below & above,
+            Overlay::Xor => below ^ above,
+        }
+    }
+}
+
+/// A black and white image, one bit per pixel
+///
+/// Rows are packed MSB-first like `SteelSeriesDisplay::framebuffer`, each row starting at a new
+/// byte, the unused bits at the end of a row are always off. The binary operators combine two
+/// frames pixel by pixel, the result has the size of the left frame and pixels outside of the
+/// right frame count as off.
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
+pub struct Frame {
+    size: Size,
+    data: Vec<u8>,
+}
+
+impl Frame {
+    /// Create a frame with all pixels off
+    #[must_use]
+    pub fn new(size: Size) -> Frame {
+        Frame {
+            size,
+            data: vec![0; stride(size) * size.height as usize],
+        }
+    }
+
+    /// Create a frame from packed rows, `None` if `data` doesn't match the size
+    #[must_use]
+    pub fn from_packed(size: Size, data: Vec<u8>) -> Option<Frame> {
+        if data.len() != stride(size) * size.height as usize {
+            return None;
+        }
+        let mut frame = Frame { size, data };
+        frame.clear_padding();
+        Some(frame)
+    }
+
+    /// The packed rows of the frame
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Color of a pixel, `None` if the point is outside of the frame
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<BinaryColor> {
+        let (index, bit) = self.position(point)?;
+        Some(BinaryColor::from(self.data[index] & bit != 0))
+    }
+
+    /// Set the color of a pixel, points outside of the frame are ignored
+    pub fn set(&mut self, point: Point, color: BinaryColor) {
+        if let Some((index, bit)) = self.position(point) {
+            if color.is_on() {
+                self.data[index] |= bit;
+            } else {
+                self.data[index] &= !bit;
+            }
+        }
+    }
+
+    /// Combine `other` into this frame with its top-left corner at `top_left`. Pixels of `other`
+    /// which fall outside of this frame are clipped.
+    pub fn overlay(&mut self, other: &Frame, top_left: Point, mode: Overlay) {
+        for y in 0..other.size.height.cast_signed() {
+            for x in 0..other.size.width.cast_signed() {
+                let target = top_left + Point::new(x, y);
+                let (Some(below), Some(above)) = (self.get(target), other.get(Point::new(x, y)))
+                else {
+                    continue;
+                };
+                let on = mode.combine(below.is_on(), above.is_on());
+                self.set(target, BinaryColor::from(on));
+            }
+        }
+    }
+
+    /// Number of pixels which are on
+    #[must_use]
+    pub fn count_on(&self) -> u32 {
+        self.data.iter().map(|byte| byte.count_ones()).sum()
+    }
+
+    // Helper which turns off the unused bits at the end of every row, so frames with the same
+    // pixels always compare equal
+    fn clear_padding(&mut self) {
+        let used = self.size.width % 8;
+        if used == 0 {
+            return;
+        }
+        let mask = !(0xFF_u8 >> used);
+        let stride = stride(self.size);
+        for row in self.data.chunks_mut(stride) {
+            row[stride - 1] &= mask;
+        }
+    }
+
+    // Helper which finds the byte and bit of a pixel
+    fn position(&self, point: Point) -> Option<(usize, u8)> {
+        let x = u32::try_from(point.x).ok()?;
+        let y = u32::try_from(point.y).ok()?;
+        if x >= self.size.width || y >= self.size.height {
+            return None;
+        }
+        let index = y as usize * stride(self.size) + x as usize / 8;
+        Some((index, 0x80 >> (x % 8)))
+    }
+
+    // Helper which combines two frames pixel by pixel
+    fn combined(&self, other: &Frame, mode: Overlay) -> Frame {
+        let mut combined = self.clone();
+        if self.size == other.size {
+            for (byte, other) in combined.data.iter_mut().zip(&other.data) {
+                *byte = match mode {
+                    Overlay::Replace => *other,
+                    Overlay::Or => *byte | other,
+                    Overlay::And => *byte & other,
+                    Overlay::Xor => *byte ^ other,
+                };
+            }
+        } else {
+            if mode == Overlay::And {
+                // pixels outside of `other` are off
+                let mut mask = Frame::new(self.size);
+                mask.overlay(other, Point::zero(), Overlay::Replace);
+                return self.combined(&mask, Overlay::And);
+            }
+            combined.overlay(other, Point::zero(), mode);
+        }
+        combined
+    }
+}
+
+// Helper which computes the bytes per row
+fn stride(size: Size) -> usize {
+    size.width.div_ceil(8) as usize
+}
+
+impl BitAnd for &Frame {
+    type Output = Frame;
+
+    fn bitand(self, other: &Frame) -> Frame {
+        self.combined(other, Overlay::And)
+    }
+}
+
+impl BitOr for &Frame {
+    type Output = Frame;
+
+    fn bitor(self, other: &Frame) -> Frame {
+        self.combined(other, Overlay::Or)
+    }
+}
+
+impl BitXor for &Frame {
+    type Output = Frame;
+
+    fn bitxor(self, other: &Frame) -> Frame {
+        self.combined(other, Overlay::Xor)
+    }
+}
+
+impl Not for &Frame {
+    type Output = Frame;
+
+    fn not(self) -> Frame {
+        let mut inverted = self.clone();
+        for byte in &mut inverted.data {
+            *byte = !*byte;
+        }
+        inverted.clear_padding();
+        inverted
+    }
+}
+
+impl From<&SteelSeriesDisplay> for Frame {
+    fn from(display: &SteelSeriesDisplay) -> Frame {
+        let size = display.size();
+        let mut frame = Frame::new(size);
+        // displays are 128 pixels wide, so their rows are packed the same way
+        let len = frame.data.len();
+        frame.data.copy_from_slice(&display.framebuffer[..len]);
+        frame
+    }
+}
+
+/// Frames are drawn at the top-left corner, replacing the content of the display
+impl FrameSource for Frame {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        if self.size == display.size() {
+            display.framebuffer.copy_from_slice(&self.data);
+            return;
+        }
+        let mut frame = Frame::from(&*display);
+        frame.overlay(self, Point::zero(), Overlay::Replace);
+        display.framebuffer.copy_from_slice(&frame.data);
+    }
+}
+
+impl OriginDimensions for Frame {
+    fn size(&self) -> Size {
+        self.size
+    }
+}
+
+impl DrawTarget for Frame {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set(point, color);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::display::SteelSeriesLCDType;
+
+    // Helper which builds a frame with the given pixels on
+    fn frame(size: Size, on: &[(i32, i32)]) -> Frame {
+        let mut frame = Frame::new(size);
+        for &(x, y) in on {
+            frame.set(Point::new(x, y), BinaryColor::On);
+        }
+        frame
+    }
+
+    #[test]
+    fn operators() {
+        let size = Size::new(10, 2);
+        let left = frame(size, &[(0, 0), (1, 0), (9, 1)]);
+        let right = frame(size, &[(1, 0), (2, 0), (9, 1)]);
+
+        assert_eq!(&left & &right, frame(size, &[(1, 0), (9, 1)]));
+        assert_eq!(
+            &left | &right,
+            frame(size, &[(0, 0), (1, 0), (2, 0), (9, 1)])
+        );
+        assert_eq!(&left ^ &right, frame(size, &[(0, 0), (2, 0)]));
+        let inverted = !&left;
+        assert_eq!(inverted.count_on(), 20 - 3);
+        // the padding of the rows stays off
+        assert_eq!(inverted.data(), &[0x3F, 0xC0, 0xFF, 0x80]);
+        assert_eq!(!&inverted, left);
+    }
+
+    #[test]
+    fn operators_with_smaller_frames() {
+        let left = frame(Size::new(4, 4), &[(0, 0), (3, 3)]);
+        let right = frame(Size::new(2, 2), &[(0, 0), (1, 1)]);
+
+        // pixels outside of the right frame count as off
+        assert_eq!(&left & &right, frame(Size::new(4, 4), &[(0, 0)]));
+        assert_eq!(
+            &left | &right,
+            frame(Size::new(4, 4), &[(0, 0), (1, 1), (3, 3)])
+        );
+        assert_eq!(&left ^ &right, frame(Size::new(4, 4), &[(1, 1), (3, 3)]));
+    }
+
+    #[test]
+    fn overlay() {
+        let size = Size::new(4, 4);
+        let icon = frame(Size::new(2, 2), &[(0, 0), (1, 1)]);
+        let below = frame(size, &[(2, 2), (3, 2)]);
+
+        let mut replaced = below.clone();
+        replaced.overlay(&icon, Point::new(2, 2), Overlay::Replace);
+        assert_eq!(replaced, frame(size, &[(2, 2), (3, 3)]));
+        let mut or = below.clone();
+        or.overlay(&icon, Point::new(2, 2), Overlay::Or);
+        assert_eq!(or, frame(size, &[(2, 2), (3, 2), (3, 3)]));
+        let mut and = below.clone();
+        and.overlay(&icon, Point::new(2, 2), Overlay::And);
+        assert_eq!(and, frame(size, &[(2, 2)]));
+        let mut xor = below.clone();
+        xor.overlay(&icon, Point::new(2, 2), Overlay::Xor);
+        assert_eq!(xor, frame(size, &[(3, 2), (3, 3)]));
+
+        // pixels outside of the frame are clipped
+        let mut clipped = below.clone();
+        clipped.overlay(&icon, Point::new(3, -1), Overlay::Or);
+        assert_eq!(clipped, frame(size, &[(2, 2), (3, 2)]));
+        clipped.overlay(&icon, Point::new(2, -1), Overlay::Or);
+        assert_eq!(clipped, frame(size, &[(2, 2), (3, 2), (3, 0)]));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        let mut display = SteelSeriesDisplay::new(SteelSeriesLCDType::Apex);
+        let _ = Pixel(Point::new(0, 0), BinaryColor::On).draw(&mut display);
+        let _ = Pixel(Point::new(127, 39), BinaryColor::On).draw(&mut display);
+
+        let mut frame = Frame::from(&display);
+        assert_eq!(frame.size(), display.size());
+        assert_eq!(frame.data(), &display.framebuffer[..]);
+        assert_eq!(frame.get(Point::new(127, 39)), Some(BinaryColor::On));
+
+        frame.set(Point::new(64, 20), BinaryColor::On);
+        let mut rendered = SteelSeriesDisplay::new(SteelSeriesLCDType::Apex);
+        frame.render(&mut rendered);
+        assert_eq!(Frame::from(&rendered), frame);
+
+        // smaller frames only replace the top-left corner
+        let mut corner = Frame::new(Size::new(2, 2));
+        corner.render(&mut rendered);
+        assert_eq!(Frame::from(&rendered).count_on(), 2);
+    }
+}
@@ -0,0 +1,137 @@
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+use embedded_graphics::{
+    image::{Image, ImageRaw},
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Line, PrimitiveStyle, Rectangle},
+};
+
+use crate::display::SteelSeriesDisplay;
+
+/// Content which can render itself onto a display
+///
+/// Used wherever the crate accepts content, e.g. for the splash and goodbye screens. It is
+/// implemented for:
+/// * closures taking a `&mut SteelSeriesDisplay`
+/// * packed raw buffers (`Vec<u8>`) in the layout of `SteelSeriesDisplay::framebuffer`
+/// * raw images (`ImageRaw<BinaryColor>`), drawn at the top-left corner
+/// * widgets and other drawables wrapped in [`Drawing`]
+///
+/// As the same source is rendered onto every display type, implementations should take the size
+/// of the display into account.
+pub trait FrameSource {
+    /// Render the content onto the display
+    fn render(&mut self, display: &mut SteelSeriesDisplay);
+}
+
+impl<F: FnMut(&mut SteelSeriesDisplay)> FrameSource for F {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        self(display);
+    }
+}
+
+/// Raw buffers are only copied onto displays with a framebuffer of exactly the same length,
+/// other displays are left untouched.
+impl FrameSource for Vec<u8> {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        if display.framebuffer.len() == self.len() {
+            display.framebuffer.copy_from_slice(self);
+        }
+    }
+}
+
+impl FrameSource for ImageRaw<'_, BinaryColor> {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        let _ = Image::new(&*self, Point::zero()).draw(display);
+    }
+}
+
+/// Wrapper which turns any drawable (e.g. a widget) into a [`FrameSource`]
+pub struct Drawing<T>(pub T);
+
+impl<T: Drawable<Color = BinaryColor>> FrameSource for Drawing<T> {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        let _ = self.0.draw(display);
+    }
+}
+
+/// Wrapper which keeps a panicking source from taking down the whole dashboard
+///
+/// If rendering the wrapped source panics, everything it drew in that frame is discarded and a
+/// crossed-out box is drawn in its bounds instead. The source is rendered again on the next
+/// frame, so it recovers as soon as it stops panicking.
+pub struct Isolated<S> {
+    source: S,
+    bounds: Option<Rectangle>,
+    panics: u64,
+}
+
+impl<S: FrameSource> Isolated<S> {
+    /// Wrap a source, its placeholder covers the whole display
+    #[must_use]
+    pub fn new(source: S) -> Isolated<S> {
+        Isolated {
+            source,
+            bounds: None,
+            panics: 0,
+        }
+    }
+
+    /// Draw the placeholder in `bounds` only, e.g. the region of a widget
+    #[must_use]
+    pub fn bounds(mut self, bounds: Rectangle) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// How often rendering the source panicked so far
+    #[must_use]
+    pub fn panics(&self) -> u64 {
+        self.panics
+    }
+
+    /// The wrapped source
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+}
+
+impl<S: FrameSource> FrameSource for Isolated<S> {
+    fn render(&mut self, display: &mut SteelSeriesDisplay) {
+        let framebuffer = display.framebuffer.clone();
+        let source = &mut self.source;
+        if catch_unwind(AssertUnwindSafe(|| source.render(display))).is_ok() {
+            return;
+        }
+        self.panics += 1;
+        println!("Warning: Rendering a frame source panicked, showing a placeholder");
+        display.framebuffer = framebuffer;
+        let bounds = self.bounds.unwrap_or_else(|| display.bounding_box());
+        let _ = placeholder(bounds, display);
+    }
+}
+
+// Helper which draws a crossed-out box into `bounds`
+fn placeholder<D: DrawTarget<Color = BinaryColor>>(
+    bounds: Rectangle,
+    target: &mut D,
+) -> Result<(), D::Error> {
+    let Some(bottom_right) = bounds.bottom_right() else {
+        return Ok(());
+    };
+    bounds
+        .into_styled(PrimitiveStyle::with_fill(BinaryColor::Off))
+        .draw(target)?;
+    let style = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+    bounds.into_styled(style).draw(target)?;
+    Line::new(bounds.top_left, bottom_right)
+        .into_styled(style)
+        .draw(target)?;
+    Line::new(
+        Point::new(bounds.top_left.x, bottom_right.y),
+        Point::new(bottom_right.x, bounds.top_left.y),
+    )
+    .into_styled(style)
+    .draw(target)
+}
@@ -0,0 +1,948 @@
+//! Handlers of GameSense events
+//!
+//! A handler tells the Engine what to do with the data of an event on a specific device, e.g.
+//! show a bitmap on a screen. Bind handlers to an event with
+//! [`GameSenseAPI::bind_game_event`](crate::GameSenseAPI::bind_game_event).
+
+use std::time::Duration;
+
+use embedded_graphics::prelude::Size;
+use serde_json::json;
+
+use crate::{
+    display::{SteelSeriesDisplay, SteelSeriesLCDType},
+    events::Icon,
+};
+
+/// Anything which can be bound to an event as a GameSense handler
+pub trait Handler {
+    /// The JSON handler definition as expected by the `bind_game_event` endpoint
+    fn to_json(&self) -> serde_json::Value;
+}
+
+/// Raw JSON handlers, for everything the typed handlers don't cover
+impl Handler for serde_json::Value {
+    fn to_json(&self) -> serde_json::Value {
+        self.clone()
+    }
+}
+
+/// Zone of a screened device a handler targets
+///
+/// Most devices only have the zone [`Zone::One`]. To show different content in different zones of
+/// the same device, bind a handler per zone to separate events.
+#[derive(PartialEq, Eq, Hash, Clone, Debug, Default)]
+pub enum Zone {
+    #[default]
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+    /// Any other zone name of the GameSense SDK
+    Other(String),
+}
+
+impl Zone {
+    /// The zone name as used by the GameSense SDK
+    #[must_use]
+    pub fn name(&self) -> &str {
+        match self {
+            Zone::One => "one",
+            Zone::Two => "two",
+            Zone::Three => "three",
+            Zone::Four => "four",
+            Zone::Five => "five",
+            Zone::Other(name) => name,
+        }
+    }
+}
+
+/// How often the Engine repeats the data of a screen handler
+///
+/// Only has an effect together with a length, e.g. [`ScreenHandler::length`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Repeat {
+    /// Loop until the next event
+    Forever,
+    /// Show the data the given number of times
+    Times(u32),
+}
+
+/// Screen handler which shows bitmaps sent with the event
+///
+/// Until the first frame arrives, the handler shows the initial frame it was created with. A
+/// handler with several frames is an animation the Engine plays on its own:
+///
+/// ```ignore
+/// let handler = ScreenHandler::bitmap(&first)
+///     .length(Duration::from_millis(200))
+///     .frame(&second, Duration::from_millis(200))
+///     .repeat(Repeat::Forever);
+/// api.bind_game_event("SPINNER", &[&handler])?;
+/// ```
+pub struct ScreenHandler {
+    lcd_type: SteelSeriesLCDType,
+    zone: Zone,
+    frames: Vec<(Vec<u8>, Option<Duration>)>,
+    length: Option<Duration>,
+    repeat: Option<Repeat>,
+}
+
+impl ScreenHandler {
+    /// Create a bitmap handler for a display type, showing the current content of `display`
+    /// until the first frame is sent
+    #[must_use]
+    pub fn bitmap(display: &SteelSeriesDisplay) -> ScreenHandler {
+        ScreenHandler {
+            lcd_type: display.lcd_type(),
+            zone: Zone::One,
+            frames: vec![(display.framebuffer.clone(), None)],
+            length: None,
+            repeat: None,
+        }
+    }
+
+    /// Show every frame only for `length`, afterwards the screen reverts to what it showed
+    /// before. Bind this to a separate event for toast-style notifications.
+    #[must_use]
+    pub fn length(mut self, length: Duration) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Append a frame showing the current content of `display` for `length`. Frames of displays
+    /// of another type than the handler are ignored.
+    #[must_use]
+    pub fn frame(mut self, display: &SteelSeriesDisplay, length: Duration) -> Self {
+        if display.lcd_type() == self.lcd_type {
+            self.frames
+                .push((display.framebuffer.clone(), Some(length)));
+        } else {
+            println!(
+                "Warning: Ignoring frame of a {:?} display for a {:?} handler",
+                display.lcd_type(),
+                self.lcd_type
+            );
+        }
+        self
+    }
+
+    /// Let the Engine repeat the frames instead of reverting after their length
+    #[must_use]
+    pub fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+
+    /// Target another zone of the device (defaults to [`Zone::One`])
+    #[must_use]
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zone = zone;
+        self
+    }
+}
+
+impl Handler for ScreenHandler {
+    fn to_json(&self) -> serde_json::Value {
+        let dimensions = self.lcd_type.dimensions();
+        let last = self.frames.len() - 1;
+        let datas: Vec<serde_json::Value> = self
+            .frames
+            .iter()
+            .enumerate()
+            .map(|(index, (image_data, length))| {
+                let mut data = json!({
+                    "has-text": false,
+                    "image-data": image_data
+                });
+                // the repetition applies to the whole sequence, so it is set on its last frame
+                let repeat = if index == last { self.repeat } else { None };
+                set_timing(&mut data, length.or(self.length), repeat);
+                data
+            })
+            .collect();
+        json!({
+            "zone": self.zone.name(),
+            "device-type": format!("screened-{}x{}", dimensions.width, dimensions.height),
+            "mode": "screen",
+            "datas": datas
+        })
+    }
+}
+
+/// One line of a [`TextScreenHandler`], rendered by the Engine
+///
+/// A line shows the value of the event, or a string from the event's frame if it has a context
+/// key. Prefix and suffix are added around it.
+#[derive(Clone, Debug, Default)]
+pub struct TextLine {
+    prefix: String,
+    suffix: String,
+    bold: bool,
+    wrap: u8,
+    context_frame_key: Option<String>,
+    arg: Option<String>,
+}
+
+impl TextLine {
+    /// A line showing the value of the event
+    #[must_use]
+    pub fn value() -> TextLine {
+        TextLine::default()
+    }
+
+    /// A line showing the string sent as `frame[key]` with the event
+    #[must_use]
+    pub fn context(key: impl Into<String>) -> TextLine {
+        TextLine {
+            context_frame_key: Some(key.into()),
+            ..TextLine::default()
+        }
+    }
+
+    /// Text shown before the value
+    #[must_use]
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Text shown after the value
+    #[must_use]
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Render the line in a bold font
+    #[must_use]
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    /// Wrap long text onto up to `lines` lines (0 or 1 disables wrapping, at most 2)
+    #[must_use]
+    pub fn wrap(mut self, lines: u8) -> Self {
+        self.wrap = lines.min(2);
+        self
+    }
+
+    /// Compute the shown text with a GoLisp expression of the Engine instead, e.g.
+    /// `(context-frame: self)` for the whole context frame
+    #[must_use]
+    pub fn arg(mut self, expression: impl Into<String>) -> Self {
+        self.arg = Some(expression.into());
+        self
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let mut line = json!({
+            "has-text": true,
+            "prefix": self.prefix,
+            "suffix": self.suffix,
+            "bold": self.bold,
+            "wrap": self.wrap,
+        });
+        if let Some(key) = &self.context_frame_key {
+            line["context-frame-key"] = key.as_str().into();
+        }
+        if let Some(arg) = &self.arg {
+            line["arg"] = arg.as_str().into();
+        }
+        line
+    }
+}
+
+/// The context key of the `n`th line (starting at 0) of [`TextScreenHandler::lines`]
+#[must_use]
+pub fn text_line_key(n: usize) -> String {
+    format!("line-{}", n + 1)
+}
+
+/// Screen handler which lets the Engine render text instead of drawing bitmaps
+///
+/// ```ignore
+/// api.bind_game_event("STATUS", &[&TextScreenHandler::lines(2)])?;
+/// api.send_text("STATUS", &["Build #42", "passed"])?;
+/// ```
+pub struct TextScreenHandler {
+    lcd_type: Option<SteelSeriesLCDType>,
+    zone: Zone,
+    lines: Vec<TextLine>,
+    icon: Icon,
+    length: Option<Duration>,
+    repeat: Option<Repeat>,
+}
+
+impl TextScreenHandler {
+    /// Create a handler rendering the given lines on every screened device
+    #[must_use]
+    pub fn new(lines: Vec<TextLine>) -> TextScreenHandler {
+        TextScreenHandler {
+            lcd_type: None,
+            zone: Zone::One,
+            lines,
+            icon: Icon::None,
+            length: None,
+            repeat: None,
+        }
+    }
+
+    /// Create a handler with `count` lines showing the strings sent with
+    /// [`GameSenseAPI::send_text`](crate::GameSenseAPI::send_text)
+    #[must_use]
+    pub fn lines(count: usize) -> TextScreenHandler {
+        TextScreenHandler::new(
+            (0..count)
+                .map(|n| TextLine::context(text_line_key(n)))
+                .collect(),
+        )
+    }
+
+    /// Only target devices with the given display type (defaults to every screened device)
+    #[must_use]
+    pub fn device(mut self, lcd_type: SteelSeriesLCDType) -> Self {
+        self.lcd_type = Some(lcd_type);
+        self
+    }
+
+    /// Target another zone of the device (defaults to [`Zone::One`])
+    #[must_use]
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zone = zone;
+        self
+    }
+
+    /// Show a built-in icon next to the text (defaults to [`Icon::None`])
+    #[must_use]
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = icon;
+        self
+    }
+
+    /// Show the text only for `length` after every event, afterwards the screen reverts to what
+    /// it showed before
+    #[must_use]
+    pub fn length(mut self, length: Duration) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Let the Engine repeat the text instead of reverting after its length
+    #[must_use]
+    pub fn repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = Some(repeat);
+        self
+    }
+}
+
+impl Handler for TextScreenHandler {
+    fn to_json(&self) -> serde_json::Value {
+        let device_type = match self.lcd_type {
+            Some(lcd_type) => {
+                let dimensions = lcd_type.dimensions();
+                format!("screened-{}x{}", dimensions.width, dimensions.height)
+            }
+            None => "screened".to_string(),
+        };
+        let mut data = match self.lines.as_slice() {
+            [line] => line.to_json(),
+            lines => json!({
+                "lines": lines.iter().map(TextLine::to_json).collect::<Vec<_>>()
+            }),
+        };
+        data["icon-id"] = self.icon.id().into();
+        set_timing(&mut data, self.length, self.repeat);
+        json!({
+            "zone": self.zone.name(),
+            "device-type": device_type,
+            "mode": "screen",
+            "datas": [data]
+        })
+    }
+}
+
+// Helper which adds the display duration and repetitions to the data of a screen handler
+fn set_timing(data: &mut serde_json::Value, length: Option<Duration>, repeat: Option<Repeat>) {
+    if let Some(length) = length {
+        data["length-millis"] = u64::try_from(length.as_millis()).unwrap_or(u64::MAX).into();
+    }
+    match repeat {
+        Some(Repeat::Forever) => data["repeats"] = true.into(),
+        Some(Repeat::Times(count)) => data["repeats"] = count.into(),
+        None => {}
+    }
+}
+
+/// A vibration of a [`TactileHandler`]
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Vibration {
+    StrongClick,
+    SharpClick,
+    SoftBump,
+    DoubleClick,
+    TripleClick,
+    Buzz,
+    LongBuzz,
+    /// Vibrate for the given duration
+    Custom(Duration),
+    /// Any other predefined pattern of the GameSense SDK, e.g. `ti_predefined_strongclick_60`
+    Other(String),
+}
+
+impl Vibration {
+    fn to_json(&self) -> serde_json::Value {
+        let name = match self {
+            Vibration::StrongClick => "ti_predefined_strongclick_100",
+            Vibration::SharpClick => "ti_predefined_sharpclick_100",
+            Vibration::SoftBump => "ti_predefined_softbump_100",
+            Vibration::DoubleClick => "ti_predefined_doubleclick_100",
+            Vibration::TripleClick => "ti_predefined_tripleclick_100",
+            Vibration::Buzz => "ti_predefined_buzz_100",
+            Vibration::LongBuzz => "ti_predefined_longbuzz_100",
+            Vibration::Custom(length) => {
+                return json!({
+                    "type": "custom",
+                    "length-ms": u64::try_from(length.as_millis()).unwrap_or(u64::MAX)
+                });
+            }
+            Vibration::Other(name) => name,
+        };
+        json!({ "type": name })
+    }
+}
+
+/// Handler which lets the tactile motor of the Rival 700/710 vibrate on every event
+///
+/// ```ignore
+/// let buzz = TactileHandler::new()
+///     .vibrate(Vibration::DoubleClick)
+///     .pause(Duration::from_millis(200))
+///     .vibrate(Vibration::Custom(Duration::from_millis(500)));
+/// let rival = api.display_rival_mut().expect("the Rival display is managed");
+/// api.bind_game_event("ALERT", &[&ScreenHandler::bitmap(rival), &buzz])?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TactileHandler {
+    zone: Zone,
+    pattern: Vec<(Vibration, Duration)>,
+}
+
+impl TactileHandler {
+    /// Create a handler without vibrations, add them with [`TactileHandler::vibrate`]
+    #[must_use]
+    pub fn new() -> TactileHandler {
+        TactileHandler::default()
+    }
+
+    /// Append a vibration to the pattern
+    #[must_use]
+    pub fn vibrate(mut self, vibration: Vibration) -> Self {
+        self.pattern.push((vibration, Duration::ZERO));
+        self
+    }
+
+    /// Wait for `delay` after the last vibration before the next one starts
+    #[must_use]
+    pub fn pause(mut self, delay: Duration) -> Self {
+        if let Some((_, pause)) = self.pattern.last_mut() {
+            *pause += delay;
+        }
+        self
+    }
+
+    /// Target another zone of the device (defaults to [`Zone::One`])
+    #[must_use]
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zone = zone;
+        self
+    }
+}
+
+impl Handler for TactileHandler {
+    fn to_json(&self) -> serde_json::Value {
+        let pattern: Vec<serde_json::Value> = self
+            .pattern
+            .iter()
+            .map(|(vibration, delay)| {
+                let mut step = vibration.to_json();
+                if !delay.is_zero() {
+                    step["delay-ms"] = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX).into();
+                }
+                step
+            })
+            .collect();
+        json!({
+            "device-type": "tactile",
+            "zone": self.zone.name(),
+            "mode": "vibrate",
+            "pattern": pattern
+        })
+    }
+}
+
+/// Custom handler code in GoLisp, the scripting language of the Engine
+///
+/// For behaviors the JSON handlers can't express. Load it with
+/// [`GameSenseAPI::load_golisp_handlers`](crate::GameSenseAPI::load_golisp_handlers), which
+/// replaces all handlers of the game, including the bound JSON handlers.
+#[derive(Clone, Debug, Default)]
+pub struct GoLisp {
+    code: String,
+}
+
+impl GoLisp {
+    /// Create handler code from source
+    #[must_use]
+    pub fn new(code: impl Into<String>) -> GoLisp {
+        GoLisp { code: code.into() }
+    }
+
+    /// Read handler code from a file, e.g. a `.lsp` file shipped with the app
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read.
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<GoLisp> {
+        std::fs::read_to_string(path).map(GoLisp::new)
+    }
+
+    /// Append more code, e.g. shared helper functions
+    #[must_use]
+    pub fn append(mut self, code: &str) -> Self {
+        if !self.code.is_empty() && !self.code.ends_with('\n') {
+            self.code.push('\n');
+        }
+        self.code.push_str(code);
+        self
+    }
+
+    /// The source code
+    #[must_use]
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+}
+
+// Device types known to the GameSense SDK besides the screened ones
+const DEVICE_TYPES: &[&str] = &[
+    "keyboard",
+    "mouse",
+    "headset",
+    "indicator",
+    "tactile",
+    "rgb-1-zone",
+    "rgb-2-zone",
+    "rgb-3-zone",
+    "rgb-4-zone",
+    "rgb-5-zone",
+    "rgb-8-zone",
+    "rgb-12-zone",
+    "rgb-17-zone",
+    "rgb-24-zone",
+    "rgb-103-zone",
+    "rgb-per-key-zones",
+];
+
+// Modes of handlers for devices which are neither screened nor tactile
+const LIGHTING_MODES: &[&str] = &[
+    "color",
+    "percent",
+    "count",
+    "context-color",
+    "bitmap",
+    "partial-bitmap",
+];
+
+// Helper which checks a handler definition against the schema of the GameSense SDK and describes
+// the first problem found
+pub(crate) fn validate(handler: &serde_json::Value) -> Result<(), String> {
+    let handler = handler
+        .as_object()
+        .ok_or("a handler must be a JSON object")?;
+    let device_type = handler
+        .get("device-type")
+        .and_then(serde_json::Value::as_str)
+        .ok_or("missing \"device-type\"")?;
+    let mode = handler.get("mode").and_then(serde_json::Value::as_str);
+
+    if device_type == "screened" || device_type.starts_with("screened-") {
+        let size = match device_type.strip_prefix("screened-") {
+            Some(size) => Some(
+                SteelSeriesLCDType::all()
+                    .iter()
+                    .map(SteelSeriesLCDType::dimensions)
+                    .find(|dimensions| {
+                        size == format!("{}x{}", dimensions.width, dimensions.height)
+                    })
+                    .ok_or_else(|| {
+                        format!("unknown screen size in device-type \"{device_type}\"")
+                    })?,
+            ),
+            None => None,
+        };
+        if mode != Some("screen") {
+            return Err("the mode of screened devices must be \"screen\"".to_string());
+        }
+        // zones other than `one` to `five` are allowed, see `Zone::Other`
+        match handler.get("zone").and_then(serde_json::Value::as_str) {
+            Some("") => return Err("the zone must not be empty".to_string()),
+            Some(_) => {}
+            None => return Err("missing \"zone\"".to_string()),
+        }
+        let datas = handler
+            .get("datas")
+            .and_then(serde_json::Value::as_array)
+            .filter(|datas| !datas.is_empty())
+            .ok_or("screen handlers need a non-empty \"datas\" array")?;
+        for (index, data) in datas.iter().enumerate() {
+            validate_screen_data(data, size)
+                .map_err(|message| format!("datas[{index}]: {message}"))?;
+        }
+        return Ok(());
+    }
+
+    if !DEVICE_TYPES.contains(&device_type) {
+        return Err(format!("unknown device-type \"{device_type}\""));
+    }
+    if mode == Some("bitmap") {
+        return if device_type == "rgb-per-key-zones" {
+            Ok(())
+        } else {
+            Err("the bitmap mode is only supported by \"rgb-per-key-zones\"".to_string())
+        };
+    }
+    if !handler
+        .get("zone")
+        .is_some_and(serde_json::Value::is_string)
+        && !handler
+            .get("custom-zone-keys")
+            .is_some_and(serde_json::Value::is_array)
+    {
+        return Err("missing \"zone\" (or \"custom-zone-keys\" array)".to_string());
+    }
+    match (device_type, mode) {
+        ("tactile", Some("vibrate")) => match handler.get("pattern") {
+            Some(serde_json::Value::Array(pattern)) if !pattern.is_empty() => Ok(()),
+            _ => Err("tactile handlers need a non-empty \"pattern\" array".to_string()),
+        },
+        ("tactile", _) => Err("the mode of tactile devices must be \"vibrate\"".to_string()),
+        (_, Some(mode)) if LIGHTING_MODES.contains(&mode) => Ok(()),
+        (_, Some(mode)) => Err(format!(
+            "unknown mode \"{mode}\" for device-type \"{device_type}\""
+        )),
+        (_, None) => Err("missing \"mode\"".to_string()),
+    }
+}
+
+// Helper which checks one entry of the datas of a screen handler
+fn validate_screen_data(data: &serde_json::Value, size: Option<Size>) -> Result<(), String> {
+    if let Some(lines) = data.get("lines") {
+        let lines = lines
+            .as_array()
+            .filter(|lines| !lines.is_empty())
+            .ok_or("\"lines\" must be a non-empty array")?;
+        if !lines
+            .iter()
+            .all(|line| line.get("has-text") == Some(&true.into()))
+        {
+            return Err("every entry of \"lines\" needs \"has-text\": true".to_string());
+        }
+    } else {
+        validate_screen_line(data, size)?;
+    }
+    if data
+        .get("length-millis")
+        .is_some_and(|length| !length.is_u64())
+    {
+        return Err("\"length-millis\" must be a non-negative integer".to_string());
+    }
+    if data
+        .get("repeats")
+        .is_some_and(|repeats| !repeats.is_boolean() && !repeats.is_u64())
+    {
+        return Err("\"repeats\" must be a boolean or a non-negative integer".to_string());
+    }
+    Ok(())
+}
+
+// Helper which checks a single text line or bitmap of a screen handler
+fn validate_screen_line(data: &serde_json::Value, size: Option<Size>) -> Result<(), String> {
+    let has_text = data
+        .get("has-text")
+        .and_then(serde_json::Value::as_bool)
+        .ok_or("missing \"has-text\"")?;
+    if !has_text {
+        let image_data = data
+            .get("image-data")
+            .and_then(serde_json::Value::as_array)
+            .ok_or("bitmaps need an \"image-data\" array")?;
+        let size = size.ok_or("bitmaps need a device-type with the screen size")?;
+        let expected = (size.width * size.height / 8) as usize;
+        if image_data.len() != expected {
+            return Err(format!(
+                "\"image-data\" has {} bytes instead of {expected}",
+                image_data.len()
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lighting::{Color, ColorHandler, Device, Rgb, ZoneCount};
+    use serde_json::Value;
+
+    // Helper which builds the JSON of a screen handler
+    fn screen(device_type: &str, zone: Option<&str>, datas: &Value) -> Value {
+        json!({ "device-type": device_type, "mode": "screen", "zone": zone, "datas": datas })
+    }
+
+    // Helper which builds a blank bitmap for the 128x40 display of the Apex
+    fn bitmap() -> Value {
+        json!({ "has-text": false, "image-data": vec![0; 640] })
+    }
+
+    // Helper which checks that every handler is rejected with the expected message
+    fn assert_rejected(cases: &[(&str, Value, &str)]) {
+        for (name, handler, expected) in cases {
+            let message = validate(handler).expect_err(name);
+            assert!(message.contains(expected), "{name}: {message}");
+        }
+    }
+
+    #[test]
+    fn accepted_handlers() {
+        let cases = [
+            (
+                "bitmap",
+                screen("screened-128x40", Some("one"), &json!([bitmap()])),
+            ),
+            (
+                "zone five",
+                screen("screened-128x40", Some("five"), &json!([bitmap()])),
+            ),
+            (
+                "other zone",
+                screen("screened-128x40", Some("six"), &json!([bitmap()])),
+            ),
+            (
+                "text on any screen",
+                screen("screened", Some("one"), &json!([{ "has-text": true }])),
+            ),
+            (
+                "lines",
+                screen(
+                    "screened-128x52",
+                    Some("one"),
+                    &json!([{ "lines": [{ "has-text": true }, { "has-text": true }] }]),
+                ),
+            ),
+            (
+                "timing",
+                screen(
+                    "screened-128x40",
+                    Some("one"),
+                    &json!([
+                        { "has-text": false, "image-data": vec![0; 640], "length-millis": 100 },
+                        { "has-text": true, "length-millis": 0, "repeats": true },
+                        { "has-text": true, "repeats": 3 },
+                    ]),
+                ),
+            ),
+            (
+                "color",
+                json!({ "device-type": "keyboard", "zone": "function-keys", "mode": "color" }),
+            ),
+            (
+                "custom zone keys",
+                json!({ "device-type": "keyboard", "custom-zone-keys": [4, 5], "mode": "percent" }),
+            ),
+            (
+                "rgb zones",
+                json!({ "device-type": "rgb-103-zone", "zone": "one", "mode": "count" }),
+            ),
+            (
+                "per-key bitmap",
+                json!({ "device-type": "rgb-per-key-zones", "mode": "bitmap" }),
+            ),
+            (
+                "vibration",
+                json!({ "device-type": "tactile", "zone": "one", "mode": "vibrate",
+                        "pattern": [{ "type": "ti_predefined_strongclick_100" }] }),
+            ),
+        ];
+        for (name, handler) in cases {
+            assert_eq!(validate(&handler), Ok(()), "{name}");
+        }
+    }
+
+    #[test]
+    fn rejected_screen_handlers() {
+        let cases = [
+            ("not an object", json!([]), "must be a JSON object"),
+            (
+                "device type",
+                json!({ "mode": "screen" }),
+                "missing \"device-type\"",
+            ),
+            (
+                "screen size",
+                screen("screened-128x64", Some("one"), &json!([bitmap()])),
+                "unknown screen size",
+            ),
+            (
+                "screen mode",
+                json!({ "device-type": "screened", "mode": "color", "zone": "one" }),
+                "must be \"screen\"",
+            ),
+            (
+                "empty zone",
+                screen("screened-128x40", Some(""), &json!([bitmap()])),
+                "must not be empty",
+            ),
+            (
+                "missing zone",
+                screen("screened-128x40", None, &json!([bitmap()])),
+                "missing \"zone\"",
+            ),
+            (
+                "empty datas",
+                screen("screened-128x40", Some("one"), &json!([])),
+                "non-empty \"datas\"",
+            ),
+            (
+                "image size",
+                screen("screened-128x36", Some("one"), &json!([bitmap()])),
+                "640 bytes instead of 576",
+            ),
+            (
+                "bitmap without size",
+                screen("screened", Some("one"), &json!([bitmap()])),
+                "need a device-type with the screen size",
+            ),
+            (
+                "missing image",
+                screen(
+                    "screened-128x40",
+                    Some("one"),
+                    &json!([{ "has-text": false }]),
+                ),
+                "\"image-data\" array",
+            ),
+            (
+                "missing has-text",
+                screen("screened-128x40", Some("one"), &json!([{}])),
+                "missing \"has-text\"",
+            ),
+            (
+                "empty lines",
+                screen("screened-128x40", Some("one"), &json!([{ "lines": [] }])),
+                "non-empty array",
+            ),
+            (
+                "bitmap line",
+                screen(
+                    "screened-128x40",
+                    Some("one"),
+                    &json!([{ "lines": [{ "has-text": false }] }]),
+                ),
+                "\"has-text\": true",
+            ),
+            (
+                "negative length",
+                screen(
+                    "screened-128x40",
+                    Some("one"),
+                    &json!([{ "has-text": true, "length-millis": -1 }]),
+                ),
+                "datas[0]: \"length-millis\"",
+            ),
+            (
+                "repeats",
+                screen(
+                    "screened-128x40",
+                    Some("one"),
+                    &json!([{ "has-text": true }, { "has-text": true, "repeats": "always" }]),
+                ),
+                "datas[1]: \"repeats\"",
+            ),
+        ];
+        assert_rejected(&cases);
+    }
+
+    #[test]
+    fn rejected_handlers() {
+        let cases = [
+            (
+                "unknown device",
+                json!({ "device-type": "toaster", "zone": "one", "mode": "color" }),
+                "unknown device-type",
+            ),
+            (
+                "unsupported zone count",
+                json!({ "device-type": "rgb-6-zone", "zone": "one", "mode": "color" }),
+                "unknown device-type",
+            ),
+            (
+                "bitmap of zoned device",
+                json!({ "device-type": "keyboard", "mode": "bitmap" }),
+                "only supported by \"rgb-per-key-zones\"",
+            ),
+            (
+                "lighting zone",
+                json!({ "device-type": "mouse", "mode": "color" }),
+                "custom-zone-keys",
+            ),
+            (
+                "tactile mode",
+                json!({ "device-type": "tactile", "zone": "one", "mode": "color" }),
+                "must be \"vibrate\"",
+            ),
+            (
+                "empty pattern",
+                json!({ "device-type": "tactile", "zone": "one", "mode": "vibrate", "pattern": [] }),
+                "non-empty \"pattern\"",
+            ),
+            (
+                "lighting mode",
+                json!({ "device-type": "mouse", "zone": "logo", "mode": "screen" }),
+                "unknown mode \"screen\"",
+            ),
+            (
+                "missing mode",
+                json!({ "device-type": "mouse", "zone": "logo" }),
+                "missing \"mode\"",
+            ),
+        ];
+        assert_rejected(&cases);
+    }
+
+    #[test]
+    fn built_handlers_are_valid() {
+        let display = SteelSeriesDisplay::new(SteelSeriesLCDType::Rival7x0);
+        let screens = [
+            ScreenHandler::bitmap(&display),
+            ScreenHandler::bitmap(&display).zone(Zone::Other("six".to_string())),
+            ScreenHandler::bitmap(&display)
+                .length(Duration::from_millis(100))
+                .frame(&display, Duration::from_millis(50))
+                .repeat(Repeat::Times(2)),
+        ];
+        for handler in &screens {
+            assert_eq!(validate(&handler.to_json()), Ok(()));
+        }
+        assert_eq!(validate(&TextScreenHandler::lines(3).to_json()), Ok(()));
+
+        for count in ZoneCount::SUPPORTED {
+            let device = Device::zones(*count).unwrap();
+            let handler = ColorHandler::new(device, "one", Color::Static(Rgb::RED));
+            assert_eq!(validate(&handler.to_json()), Ok(()), "{count} zones");
+        }
+        assert_eq!(Device::zones(6), None);
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    sync::{
+        Arc, Condvar, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::error::Error;
+
+// State shared by the heartbeat thread, its handle and the API
+pub(crate) struct Heartbeat {
+    stopped: Mutex<bool>,
+    wake: Condvar,
+    paused: AtomicBool,
+    address: Mutex<String>,
+    failures: AtomicU64,
+    last_error: Mutex<Option<Error>>,
+}
+
+impl Heartbeat {
+    pub(crate) fn new(address: String) -> Heartbeat {
+        Heartbeat {
+            stopped: Mutex::new(false),
+            wake: Condvar::new(),
+            paused: AtomicBool::new(false),
+            address: Mutex::new(address),
+            failures: AtomicU64::new(0),
+            last_error: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        !*self.stopped.lock().unwrap()
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn stop(&self) {
+        *self.stopped.lock().unwrap() = true;
+        self.wake.notify_all();
+    }
+
+    // Sleep for `timeout`, returns early once the heartbeat is stopped
+    pub(crate) fn wait(&self, timeout: Duration) {
+        let stopped = self.stopped.lock().unwrap();
+        let _ = self
+            .wake
+            .wait_timeout_while(stopped, timeout, |stopped| !*stopped);
+    }
+
+    pub(crate) fn address(&self) -> String {
+        self.address.lock().unwrap().clone()
+    }
+
+    pub(crate) fn set_address(&self, address: String) {
+        *self.address.lock().unwrap() = address;
+    }
+
+    // Keep the error of a failed heartbeat until the handle takes it
+    pub(crate) fn report(&self, result: Result<(), Error>) {
+        if let Err(err) = result {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+            *self.last_error.lock().unwrap() = Some(err);
+        }
+    }
+}
+
+/// Controls the heartbeat thread started by `GameSenseAPI::register_heartbeat()`
+///
+/// Dropping the handle leaves the heartbeat running, use [`HeartbeatHandle::stop`] or
+/// `GameSenseAPI::unregister_heartbeat()` to end it.
+pub struct HeartbeatHandle {
+    heartbeat: Arc<Heartbeat>,
+    thread: JoinHandle<()>,
+}
+
+impl HeartbeatHandle {
+    pub(crate) fn new(heartbeat: Arc<Heartbeat>, thread: JoinHandle<()>) -> HeartbeatHandle {
+        HeartbeatHandle { heartbeat, thread }
+    }
+
+    /// Stop sending heartbeats until [`HeartbeatHandle::resume`] is called, e.g. while the app
+    /// wants the Engine to revert the screen
+    pub fn pause(&self) {
+        self.heartbeat.paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Continue sending heartbeats after [`HeartbeatHandle::pause`]
+    pub fn resume(&self) {
+        self.heartbeat.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether the heartbeat is paused
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.heartbeat.is_paused()
+    }
+
+    /// Whether the heartbeat thread is still sending heartbeats (or paused)
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.heartbeat.is_running() && !self.thread.is_finished()
+    }
+
+    /// End the heartbeat thread, it exits without waiting for the next interval
+    pub fn stop(&self) {
+        self.heartbeat.stop();
+    }
+
+    /// Stop the heartbeat and wait until the thread exited
+    pub fn join(self) {
+        self.heartbeat.stop();
+        let _ = self.thread.join();
+    }
+
+    /// Number of heartbeats which failed so far
+    #[must_use]
+    pub fn failures(&self) -> u64 {
+        self.heartbeat.failures.load(Ordering::Relaxed)
+    }
+
+    /// The error of the latest failed heartbeat, `None` if no heartbeat failed since the last call
+    ///
+    /// # Panics
+    ///
+    /// Panics if the heartbeat thread panicked while storing an error.
+    #[must_use]
+    pub fn take_error(&self) -> Option<Error> {
+        self.heartbeat.last_error.lock().unwrap().take()
+    }
+}
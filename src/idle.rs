@@ -0,0 +1,93 @@
+//! User-idle detection
+//!
+//! Reports how long the user has not touched keyboard or mouse, so expensive work (audio capture,
+//! high-frequency polling, ...) can be paused while nobody is looking at the screen.
+//!
+//! Only available with the `idle` feature.
+
+use std::time::Duration;
+
+/// Time since the last keyboard or mouse input, `None` if it can't be determined on this platform
+#[must_use]
+pub fn idle_time() -> Option<Duration> {
+    platform::idle_time()
+}
+
+/// Tracks whether the user is away
+pub struct IdleWatcher {
+    threshold: Duration,
+}
+
+impl IdleWatcher {
+    /// Create a watcher which considers the user idle after `threshold` without input
+    #[must_use]
+    pub fn new(threshold: Duration) -> IdleWatcher {
+        IdleWatcher { threshold }
+    }
+
+    /// Whether the user has been idle for longer than the threshold. Returns false if the idle time
+    /// can't be determined, so nothing is paused by accident.
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        idle_time().is_some_and(|idle| idle >= self.threshold)
+    }
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::time::Duration;
+
+    use windows_sys::Win32::{
+        System::SystemInformation::GetTickCount,
+        UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO},
+    };
+
+    pub(super) fn idle_time() -> Option<Duration> {
+        let mut info = LASTINPUTINFO {
+            cbSize: u32::try_from(std::mem::size_of::<LASTINPUTINFO>()).ok()?,
+            dwTime: 0,
+        };
+        // SAFETY: `info` is a valid LASTINPUTINFO with `cbSize` set as required
+        if unsafe { GetLastInputInfo(&raw mut info) } == 0 {
+            return None;
+        }
+        // SAFETY: GetTickCount has no preconditions
+        let now = unsafe { GetTickCount() };
+        // both values are milliseconds since boot and wrap around after ~49 days
+        Some(Duration::from_millis(u64::from(
+            now.wrapping_sub(info.dwTime),
+        )))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use std::time::Duration;
+
+    // kCGEventSourceStateCombinedSessionState
+    const COMBINED_SESSION_STATE: i32 = 0;
+    // kCGAnyInputEventType
+    const ANY_INPUT_EVENT: u32 = u32::MAX;
+
+    #[link(name = "CoreGraphics", kind = "framework")]
+    unsafe extern "C" {
+        fn CGEventSourceSecondsSinceLastEventType(source_state: i32, event_type: u32) -> f64;
+    }
+
+    pub(super) fn idle_time() -> Option<Duration> {
+        // SAFETY: the function only reads the event source state
+        let seconds = unsafe {
+            CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT)
+        };
+        Duration::try_from_secs_f64(seconds).ok()
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+mod platform {
+    use std::time::Duration;
+
+    pub(super) fn idle_time() -> Option<Duration> {
+        None
+    }
+}
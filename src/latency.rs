@@ -0,0 +1,245 @@
+//! Connection health monitoring for game servers
+//!
+//! [`LatencyMonitor`] measures the time it takes to open a TCP connection to each configured host.
+//! This works without elevated privileges (unlike ICMP pings) and keeps a sliding window of samples
+//! per host to derive latency, jitter and packet loss. [`LatencyWidget`] renders these statistics
+//! with a color-free severity encoding.
+//!
+//! Only available with the `latency` feature.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Baseline, Text},
+};
+
+/// Measures latency, jitter and loss towards a list of hosts
+pub struct LatencyMonitor {
+    hosts: Vec<String>,
+    samples: HashMap<String, VecDeque<Option<Duration>>>,
+    addresses: HashMap<String, SocketAddr>,
+    window: usize,
+    timeout: Duration,
+}
+
+impl LatencyMonitor {
+    /// Create a new monitor
+    /// # Arguments
+    ///
+    /// * `hosts` - Hosts to probe, each as `host:port` (e.g. `"euw.example.com:443"`)
+    ///
+    pub fn new<I, S>(hosts: I) -> LatencyMonitor
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        LatencyMonitor {
+            hosts: hosts.into_iter().map(Into::into).collect(),
+            samples: HashMap::new(),
+            addresses: HashMap::new(),
+            window: 20,
+            timeout: Duration::from_secs(1),
+        }
+    }
+
+    /// Set the number of samples per host used for the statistics (defaults to 20)
+    #[must_use]
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self
+    }
+
+    /// Set the time after which a probe counts as lost (defaults to 1 second)
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The probed hosts
+    #[must_use]
+    pub fn hosts(&self) -> &[String] {
+        &self.hosts
+    }
+
+    /// Probe every host once. Blocks for up to the configured timeout per host.
+    ///
+    /// Host names are resolved on the first probe and again after a lost probe, in case the server
+    /// moved. The lookup blocks on the DNS resolver of the system and is not covered by the
+    /// timeout. If a name resolves to several addresses, only the first one is probed.
+    pub fn probe(&mut self) {
+        for host in &self.hosts {
+            let sample = match self.addresses.get(host) {
+                Some(address) => probe_address(address, self.timeout),
+                None => resolve(host).and_then(|address| {
+                    self.addresses.insert(host.clone(), address);
+                    probe_address(&address, self.timeout)
+                }),
+            };
+            if sample.is_none() {
+                self.addresses.remove(host);
+            }
+            let samples = self.samples.entry(host.clone()).or_default();
+            samples.push_back(sample);
+            while samples.len() > self.window {
+                samples.pop_front();
+            }
+        }
+    }
+
+    /// Statistics for a host, `None` if it has not been probed yet
+    #[must_use]
+    pub fn stats(&self, host: &str) -> Option<LatencyStats> {
+        let samples = self
+            .samples
+            .get(host)
+            .filter(|samples| !samples.is_empty())?;
+        let successful: Vec<Duration> = samples.iter().flatten().copied().collect();
+        let jitter = if successful.len() > 1 {
+            let total: Duration = successful
+                .windows(2)
+                .map(|pair| pair[0].abs_diff(pair[1]))
+                .sum();
+            total / u32::try_from(successful.len() - 1).unwrap_or(u32::MAX)
+        } else {
+            Duration::ZERO
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let loss = (samples.len() - successful.len()) as f32 / samples.len() as f32;
+        Some(LatencyStats {
+            latency: samples.back().copied().flatten(),
+            jitter,
+            loss,
+        })
+    }
+}
+
+// Helper which looks up the first address of a host, `None` if it could not be resolved
+fn resolve(host: &str) -> Option<SocketAddr> {
+    host.to_socket_addrs().ok()?.next()
+}
+
+// Helper which measures the time needed to open a TCP connection, `None` if it failed
+fn probe_address(address: &SocketAddr, timeout: Duration) -> Option<Duration> {
+    let start = Instant::now();
+    TcpStream::connect_timeout(address, timeout).ok()?;
+    Some(start.elapsed())
+}
+
+/// Connection health of a single host
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyStats {
+    /// Latency of the most recent probe, `None` if it was lost
+    pub latency: Option<Duration>,
+    /// Mean difference between consecutive successful probes
+    pub jitter: Duration,
+    /// Fraction of lost probes within the window (0.0..=1.0)
+    pub loss: f32,
+}
+
+/// How healthy a connection is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Low latency and no loss
+    Good,
+    /// Noticeable latency, jitter or occasional loss
+    Degraded,
+    /// High latency, heavy loss or the last probe failed
+    Bad,
+}
+
+impl LatencyStats {
+    /// Classify the statistics with the default thresholds (latency 60/120ms, jitter 15/30ms,
+    /// loss 1%/5%)
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        let Some(latency) = self.latency else {
+            return Severity::Bad;
+        };
+        let millis = latency.as_millis();
+        let jitter = self.jitter.as_millis();
+        if millis >= 120 || jitter >= 30 || self.loss >= 0.05 {
+            Severity::Bad
+        } else if millis >= 60 || jitter >= 15 || self.loss >= 0.01 {
+            Severity::Degraded
+        } else {
+            Severity::Good
+        }
+    }
+}
+
+/// A single line showing latency, jitter and loss of a host
+///
+/// The severity is encoded without colors: degraded connections are underlined with a dotted
+/// line and bad connections are drawn inverted.
+pub struct LatencyWidget<'a> {
+    label: &'a str,
+    stats: LatencyStats,
+    top_left: Point,
+    style: MonoTextStyle<'a, BinaryColor>,
+}
+
+impl<'a> LatencyWidget<'a> {
+    /// Create a new widget
+    #[must_use]
+    pub fn new(
+        label: &'a str,
+        stats: LatencyStats,
+        top_left: Point,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> LatencyWidget<'a> {
+        LatencyWidget {
+            label,
+            stats,
+            top_left,
+            style,
+        }
+    }
+
+    fn text(&self) -> String {
+        let latency = self.stats.latency.map_or_else(
+            || "---".to_string(),
+            |latency| latency.as_millis().to_string(),
+        );
+        format!(
+            "{} {latency}ms ±{} {:.0}%",
+            self.label,
+            self.stats.jitter.as_millis(),
+            self.stats.loss * 100.0
+        )
+    }
+}
+
+impl Drawable for LatencyWidget<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let text = self.text();
+        let severity = self.stats.severity();
+        let mut style = self.style;
+        if severity == Severity::Bad {
+            style.text_color = Some(BinaryColor::Off);
+            style.background_color = Some(BinaryColor::On);
+        }
+        let end = Text::with_baseline(&text, self.top_left, style, Baseline::Top).draw(target)?;
+        if severity == Severity::Degraded {
+            let y = self.top_left.y + style.font.character_size.height.cast_signed();
+            let dots = (self.top_left.x..end.x)
+                .step_by(2)
+                .map(|x| Pixel(Point::new(x, y), BinaryColor::On));
+            target.draw_iter(dots)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,137 @@
+//! Common screen regions
+//!
+//! [`Screen`] computes the rectangles of typical layouts for a display type, so layout code
+//! adapts to all four resolutions instead of hardcoding coordinates.
+//!
+//! ```ignore
+//! let screen = Screen::of(SteelSeriesLCDType::Apex);
+//! let title = screen.top_bar(10);
+//! let (chart, value) = Screen::region(screen.below_top_bar(10)).split_h(0.7);
+//! ```
+
+use embedded_graphics::{geometry::AnchorY, prelude::*, primitives::Rectangle};
+
+use crate::display::SteelSeriesLCDType;
+
+/// Regions of a screen, all of them as `Rectangle`s within [`Screen::full`]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Screen {
+    area: Rectangle,
+}
+
+impl Screen {
+    /// The whole screen of a display type
+    #[must_use]
+    pub fn of(lcd_type: SteelSeriesLCDType) -> Screen {
+        Screen {
+            area: Rectangle::new(Point::zero(), lcd_type.dimensions()),
+        }
+    }
+
+    /// A region of a screen, e.g. to split one of the halves again
+    #[must_use]
+    pub fn region(area: Rectangle) -> Screen {
+        Screen { area }
+    }
+
+    /// The whole area
+    #[must_use]
+    pub fn full(&self) -> Rectangle {
+        self.area
+    }
+
+    /// The area shrunk by `margin` pixels on every side
+    #[must_use]
+    pub fn inset(&self, margin: u32) -> Rectangle {
+        self.area.offset(-margin.cast_signed())
+    }
+
+    /// A bar of `height` pixels at the top
+    #[must_use]
+    pub fn top_bar(&self, height: u32) -> Rectangle {
+        self.area
+            .resized_height(height.min(self.area.size.height), AnchorY::Top)
+    }
+
+    /// A bar of `height` pixels at the bottom
+    #[must_use]
+    pub fn bottom_bar(&self, height: u32) -> Rectangle {
+        self.area
+            .resized_height(height.min(self.area.size.height), AnchorY::Bottom)
+    }
+
+    /// The area below a top bar of `height` pixels
+    #[must_use]
+    pub fn below_top_bar(&self, height: u32) -> Rectangle {
+        let height = height.min(self.area.size.height);
+        self.area
+            .resized_height(self.area.size.height - height, AnchorY::Bottom)
+    }
+
+    /// Split into a left and a right part, the left one getting `ratio` (0.0..=1.0) of the width
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn split_h(&self, ratio: f32) -> (Rectangle, Rectangle) {
+        let width = self.area.size.width;
+        let left = ((width as f32 * ratio.clamp(0.0, 1.0)).round() as u32).min(width);
+        (
+            Rectangle::new(self.area.top_left, Size::new(left, self.area.size.height)),
+            Rectangle::new(
+                self.area.top_left + Point::new(left.cast_signed(), 0),
+                Size::new(width - left, self.area.size.height),
+            ),
+        )
+    }
+
+    /// Split into a top and a bottom part, the top one getting `ratio` (0.0..=1.0) of the height
+    #[must_use]
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    pub fn split_v(&self, ratio: f32) -> (Rectangle, Rectangle) {
+        let height = self.area.size.height;
+        let top = ((height as f32 * ratio.clamp(0.0, 1.0)).round() as u32).min(height);
+        (
+            Rectangle::new(self.area.top_left, Size::new(self.area.size.width, top)),
+            Rectangle::new(
+                self.area.top_left + Point::new(0, top.cast_signed()),
+                Size::new(self.area.size.width, height - top),
+            ),
+        )
+    }
+
+    /// The largest square at the left edge, e.g. for an icon
+    #[must_use]
+    pub fn left_square(&self) -> Rectangle {
+        Rectangle::new(self.area.top_left, Size::new_equal(self.side()))
+    }
+
+    /// The largest square at the right edge, e.g. for an icon
+    #[must_use]
+    pub fn right_square(&self) -> Rectangle {
+        let side = self.side();
+        Rectangle::new(
+            self.area.top_left + Point::new((self.area.size.width - side).cast_signed(), 0),
+            Size::new_equal(side),
+        )
+    }
+
+    /// The area left of [`Screen::right_square`]
+    #[must_use]
+    pub fn left_of_square(&self) -> Rectangle {
+        Rectangle::new(
+            self.area.top_left,
+            Size::new(self.area.size.width - self.side(), self.area.size.height),
+        )
+    }
+
+    fn side(&self) -> u32 {
+        self.area.size.width.min(self.area.size.height)
+    }
+}
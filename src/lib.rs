@@ -7,8 +7,82 @@
 //! It only supports LCD-related things and as it uses the official GameSense API,
 //! thus it only works for macOS and windows and it is required to have the SteelSeries
 //! GG Application running.
+//!
+//! Optional subsystems are behind cargo features, none of which are enabled by default:
+//! `async`, `capture`, `cjk-font`, `clipboard`, `font-pack`, `idle`, `intl-font`, `latency`,
+//! `livesplit`, `mock`, `recording`, `secrets`, `slideshow`, `telemetry`, `toml`, `totp` and
+//! `world-clock` (or `full` for all of them).
 
+mod adapt;
+pub mod alert;
 mod api;
+#[cfg(feature = "async")]
+mod async_api;
+pub mod attention;
+mod batch;
+mod budget;
+mod builder;
+#[cfg(feature = "capture")]
+pub mod capture;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+mod clock;
+mod config;
 mod display;
+mod error;
+pub mod events;
+mod features;
+#[cfg(feature = "intl-font")]
+pub mod font;
+#[cfg(feature = "font-pack")]
+pub mod font_pack;
+pub mod frame;
+mod frame_source;
+pub mod handlers;
+mod heartbeat;
+#[cfg(feature = "idle")]
+pub mod idle;
+#[cfg(feature = "latency")]
+pub mod latency;
+pub mod layout;
+pub mod lighting;
+#[cfg(feature = "livesplit")]
+pub mod livesplit;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod profiler;
+#[cfg(feature = "recording")]
+pub mod recording;
+#[cfg(feature = "secrets")]
+pub mod secrets;
+#[cfg(feature = "slideshow")]
+pub mod slideshow;
+mod stats;
+pub mod storage;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod text;
+#[cfg(feature = "totp")]
+pub mod totp;
+#[cfg(feature = "cjk-font")]
+pub mod unifont;
+mod wake;
+pub mod widgets;
+#[cfg(feature = "world-clock")]
+pub mod world_clock;
 
+pub use crate::adapt::{Adapt, Adapted};
 pub use crate::api::GameSenseAPI;
+#[cfg(feature = "async")]
+pub use crate::async_api::AsyncGameSenseAPI;
+pub use crate::batch::Batch;
+pub use crate::budget::MemoryBudget;
+pub use crate::builder::GameSenseAPIBuilder;
+pub use crate::clock::Clock;
+pub use crate::config::Config;
+pub use crate::display::{SteelSeriesDisplay, SteelSeriesLCDType};
+pub use crate::error::Error;
+pub use crate::frame_source::{Drawing, FrameSource, Isolated};
+pub use crate::heartbeat::HeartbeatHandle;
+pub use crate::stats::{Activity, SessionStats};
+pub use crate::wake::WakeDetector;
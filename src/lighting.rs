@@ -0,0 +1,301 @@
+//! Handlers for the RGB lighting of SteelSeries devices
+//!
+//! Lighting handlers are bound to events like the screen handlers, so the same game registration
+//! can drive the displays and the illumination of keyboards, mice and headsets:
+//!
+//! ```ignore
+//! let health = ColorHandler::new(
+//!     Device::Keyboard,
+//!     "function-keys",
+//!     Color::Gradient { zero: Rgb::RED, hundred: Rgb::GREEN },
+//! )
+//! .mode(Mode::Percent);
+//! api.bind_game_event("HEALTH", &[&health])?;
+//! api.send_event_data("HEALTH", json!({ "value": 75 }))?;
+//! ```
+//!
+//! Keyboards with per-key lighting can also show a full [`KeyBitmap`], sent in the same event as
+//! the frame of the displays, see [`GameSenseAPI::key_bitmap_mut`](crate::GameSenseAPI::key_bitmap_mut).
+
+use serde_json::json;
+
+use crate::handlers::Handler;
+
+/// A color of the GameSense SDK
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb::new(0, 0, 0);
+    pub const WHITE: Rgb = Rgb::new(255, 255, 255);
+    pub const RED: Rgb = Rgb::new(255, 0, 0);
+    pub const GREEN: Rgb = Rgb::new(0, 255, 0);
+    pub const BLUE: Rgb = Rgb::new(0, 0, 255);
+
+    #[must_use]
+    pub const fn new(red: u8, green: u8, blue: u8) -> Rgb {
+        Rgb { red, green, blue }
+    }
+
+    fn to_json(self) -> serde_json::Value {
+        json!({ "red": self.red, "green": self.green, "blue": self.blue })
+    }
+}
+
+/// Lit devices which can be targeted
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Device {
+    /// Any keyboard, with zones like `main-keyboard` or `function-keys`
+    Keyboard,
+    /// Any mouse, with zones like `logo` or `wheel`
+    Mouse,
+    /// Any headset, with the zone `earcups`
+    Headset,
+    /// Any device with a single indicator light, with the zone `one`
+    Indicator,
+    /// Devices with the given number of RGB zones, with the zones `one`, `two`, ... (see
+    /// [`Device::zones`])
+    Zones(ZoneCount),
+    /// Keyboards with per-key lighting, with zones like `all` or `number-keys`
+    PerKey,
+}
+
+impl Device {
+    /// A device with `count` RGB zones, `None` unless the GameSense SDK supports the count (1 to
+    /// 5, 8, 12, 17, 24 or 103)
+    #[must_use]
+    pub fn zones(count: u8) -> Option<Device> {
+        ZoneCount::new(count).map(Device::Zones)
+    }
+
+    /// The device-type as used by the GameSense SDK
+    #[must_use]
+    pub fn device_type(self) -> String {
+        match self {
+            Device::Keyboard => "keyboard".to_string(),
+            Device::Mouse => "mouse".to_string(),
+            Device::Headset => "headset".to_string(),
+            Device::Indicator => "indicator".to_string(),
+            Device::Zones(count) => format!("rgb-{}-zone", count.get()),
+            Device::PerKey => "rgb-per-key-zones".to_string(),
+        }
+    }
+}
+
+/// A number of RGB zones which is supported by the GameSense SDK
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ZoneCount(u8);
+
+impl ZoneCount {
+    /// The supported counts
+    pub const SUPPORTED: &'static [u8] = &[1, 2, 3, 4, 5, 8, 12, 17, 24, 103];
+
+    /// Check a zone count, `None` if the GameSense SDK doesn't support it
+    #[must_use]
+    pub fn new(count: u8) -> Option<ZoneCount> {
+        ZoneCount::SUPPORTED
+            .contains(&count)
+            .then_some(ZoneCount(count))
+    }
+
+    /// The number of zones
+    #[must_use]
+    pub fn get(self) -> u8 {
+        self.0
+    }
+}
+
+/// The color a handler shows, depending on the value of the event
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Color {
+    /// Always the same color
+    Static(Rgb),
+    /// A color between `zero` (value 0) and `hundred` (value 100)
+    Gradient { zero: Rgb, hundred: Rgb },
+    /// The color of the range (`low..=high`) containing the value, black outside of all ranges
+    Ranges(Vec<(i32, i32, Rgb)>),
+}
+
+impl Color {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Color::Static(color) => color.to_json(),
+            Color::Gradient { zero, hundred } => json!({
+                "gradient": { "zero": zero.to_json(), "hundred": hundred.to_json() }
+            }),
+            Color::Ranges(ranges) => ranges
+                .iter()
+                .map(|(low, high, color)| {
+                    json!({ "low": low, "high": high, "color": color.to_json() })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// How the value of the event is shown
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum Mode {
+    /// The whole zone shows the color
+    #[default]
+    Color,
+    /// The value (0 to 100) is shown as the share of lit keys in the zone
+    Percent,
+    /// The value is shown as the number of lit keys in the zone
+    Count,
+}
+
+impl Mode {
+    fn name(self) -> &'static str {
+        match self {
+            Mode::Color => "color",
+            Mode::Percent => "percent",
+            Mode::Count => "count",
+        }
+    }
+}
+
+/// Handler lighting a zone of a device
+pub struct ColorHandler {
+    device: Device,
+    zone: String,
+    color: Color,
+    mode: Mode,
+    frequency: Option<f32>,
+    repeat_limit: Option<u32>,
+}
+
+impl ColorHandler {
+    /// Create a handler showing `color` on `zone` of `device`
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The type of device which will be targeted
+    /// * `zone` - A zone name of the GameSense SDK for this device type, e.g. `logo` for mice
+    /// * `color` - The color, possibly depending on the value of the event
+    #[must_use]
+    pub fn new(device: Device, zone: impl Into<String>, color: Color) -> ColorHandler {
+        ColorHandler {
+            device,
+            zone: zone.into(),
+            color,
+            mode: Mode::Color,
+            frequency: None,
+            repeat_limit: None,
+        }
+    }
+
+    /// Show the value of the event as the number or share of lit keys (defaults to [`Mode::Color`])
+    #[must_use]
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Flash the color `frequency` times per second
+    #[must_use]
+    pub fn flash(mut self, frequency: f32) -> Self {
+        self.frequency = Some(frequency);
+        self
+    }
+
+    /// Stop flashing after `count` flashes, afterwards the color stays lit
+    #[must_use]
+    pub fn repeat_limit(mut self, count: u32) -> Self {
+        self.repeat_limit = Some(count);
+        self
+    }
+}
+
+impl Handler for ColorHandler {
+    fn to_json(&self) -> serde_json::Value {
+        let mut handler = json!({
+            "device-type": self.device.device_type(),
+            "mode": self.mode.name(),
+            "zone": self.zone,
+            "color": self.color.to_json(),
+        });
+        if let Some(frequency) = self.frequency {
+            let mut rate = json!({ "frequency": frequency });
+            if let Some(count) = self.repeat_limit {
+                rate["repeat_limit"] = count.into();
+            }
+            handler["rate"] = rate;
+        }
+        handler
+    }
+}
+
+/// Per-key colors of a keyboard, laid out as a grid of 22 columns and 6 rows
+///
+/// The grid follows the GameSense SDK: row 0 holds the escape and function keys, column 0 the
+/// leftmost keys. Keys which don't exist on a keyboard are ignored.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct KeyBitmap {
+    keys: Vec<Rgb>,
+}
+
+impl KeyBitmap {
+    /// Number of columns of the grid
+    pub const COLUMNS: usize = 22;
+    /// Number of rows of the grid
+    pub const ROWS: usize = 6;
+
+    /// Create a bitmap with all keys off
+    #[must_use]
+    pub fn new() -> KeyBitmap {
+        KeyBitmap {
+            keys: vec![Rgb::BLACK; Self::COLUMNS * Self::ROWS],
+        }
+    }
+
+    /// Set all keys to the same color
+    pub fn fill(&mut self, color: Rgb) {
+        self.keys.fill(color);
+    }
+
+    /// Set the color of a key, keys outside of the grid are ignored
+    pub fn set(&mut self, column: usize, row: usize, color: Rgb) {
+        if column < Self::COLUMNS && row < Self::ROWS {
+            self.keys[row * Self::COLUMNS + column] = color;
+        }
+    }
+
+    /// The color of a key, `None` outside of the grid
+    #[must_use]
+    pub fn get(&self, column: usize, row: usize) -> Option<Rgb> {
+        (column < Self::COLUMNS && row < Self::ROWS)
+            .then(|| self.keys[row * Self::COLUMNS + column])
+    }
+
+    // The bitmap as expected in the frame of an event
+    pub(crate) fn to_json(&self) -> serde_json::Value {
+        self.keys
+            .iter()
+            .map(|key| json!([key.red, key.green, key.blue]))
+            .collect()
+    }
+}
+
+impl Default for KeyBitmap {
+    fn default() -> Self {
+        KeyBitmap::new()
+    }
+}
+
+/// Handler showing the [`KeyBitmap`] sent as `frame.bitmap` with the event on per-key keyboards
+#[derive(Default)]
+pub struct BitmapHandler;
+
+impl Handler for BitmapHandler {
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "device-type": Device::PerKey.device_type(),
+            "mode": "bitmap"
+        })
+    }
+}
@@ -0,0 +1,174 @@
+//! Speedrun split timer backed by the LiveSplit Server
+//!
+//! [`LiveSplitClient`] talks to the TCP server of LiveSplit (the "LiveSplit Server" component,
+//! listening on port 16834 by default) and [`SplitTimer`] renders the polled state as a screen
+//! showing the current split, the delta to the comparison and the total time.
+//!
+//! Only available with the `livesplit` feature.
+
+use std::{
+    io::{BufRead, BufReader, Error, ErrorKind, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        iso_8859_1::{FONT_6X10, FONT_10X20},
+    },
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+/// Default address of the LiveSplit Server
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:16834";
+
+/// Snapshot of the timer state
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SplitState {
+    /// Name of the current split
+    pub split_name: String,
+    /// Index of the current split, -1 if the timer is not running
+    pub split_index: i32,
+    /// Delta to the comparison, as formatted by LiveSplit (e.g. `-1.23`)
+    pub delta: String,
+    /// Total time of the run, as formatted by LiveSplit
+    pub current_time: String,
+}
+
+/// Client for the LiveSplit Server protocol
+///
+/// After a failed query, e.g. a timeout, the client reconnects on the next query, so a late
+/// response can't be mistaken for the response to the next command.
+pub struct LiveSplitClient {
+    address: SocketAddr,
+    connection: Option<(TcpStream, BufReader<TcpStream>)>,
+}
+
+impl LiveSplitClient {
+    /// Connect to a LiveSplit Server, e.g. at [`DEFAULT_ADDRESS`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection could not be established.
+    pub fn connect<A: ToSocketAddrs>(address: A) -> Result<LiveSplitClient, Error> {
+        let writer = TcpStream::connect(address)?;
+        Ok(LiveSplitClient {
+            address: writer.peer_addr()?,
+            connection: Some(open(writer)?),
+        })
+    }
+
+    // send a single command and read the one-line response
+    fn query(&mut self, command: &str) -> Result<String, Error> {
+        let (writer, reader) = match &mut self.connection {
+            Some(connection) => connection,
+            None => self
+                .connection
+                .insert(open(TcpStream::connect(self.address)?)?),
+        };
+        let result = exchange(writer, reader, command);
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+
+    /// Poll the current state of the timer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the server could not be reached, did not respond in time or sent an
+    /// invalid split index.
+    pub fn poll(&mut self) -> Result<SplitState, Error> {
+        Ok(SplitState {
+            split_name: self.query("getcurrentsplitname")?,
+            split_index: parse_index(&self.query("getsplitindex")?)?,
+            delta: self.query("getdelta")?,
+            current_time: self.query("getcurrenttime")?,
+        })
+    }
+}
+
+// Helper which sets up a connection to the server
+fn open(writer: TcpStream) -> Result<(TcpStream, BufReader<TcpStream>), Error> {
+    writer.set_read_timeout(Some(Duration::from_secs(1)))?;
+    let reader = BufReader::new(writer.try_clone()?);
+    Ok((writer, reader))
+}
+
+// Helper which sends a command and reads the one-line response
+fn exchange(
+    writer: &mut TcpStream,
+    reader: &mut BufReader<TcpStream>,
+    command: &str,
+) -> Result<String, Error> {
+    writer.write_all(format!("{command}\r\n").as_bytes())?;
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "LiveSplit Server closed the connection",
+        ));
+    }
+    Ok(line.trim_end().to_string())
+}
+
+// Helper which parses the split index sent by the server
+fn parse_index(index: &str) -> Result<i32, Error> {
+    index.parse().map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("LiveSplit Server sent an invalid split index \"{index}\""),
+        )
+    })
+}
+
+/// Screen layout for a [`SplitState`]: split name and delta in the top row, the total time in a
+/// large font below
+pub struct SplitTimer<'a> {
+    state: &'a SplitState,
+    area: Rectangle,
+}
+
+impl<'a> SplitTimer<'a> {
+    /// Create a split timer screen filling `area`
+    #[must_use]
+    pub fn new(state: &'a SplitState, area: Rectangle) -> SplitTimer<'a> {
+        SplitTimer { state, area }
+    }
+}
+
+impl Drawable for SplitTimer<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let small = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let large = MonoTextStyle::new(&FONT_10X20, BinaryColor::On);
+        let top_left = self.area.top_left;
+        let top_right = top_left + Point::new(self.area.size.width.cast_signed() - 1, 0);
+
+        Text::with_baseline(&self.state.split_name, top_left, small, Baseline::Top).draw(target)?;
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        Text::with_text_style(&self.state.delta, top_right, small, right).draw(target)?;
+
+        let bottom_right = self.area.bottom_right().unwrap_or(top_right);
+        let right_bottom = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Bottom)
+            .build();
+        Text::with_text_style(&self.state.current_time, bottom_right, large, right_bottom)
+            .draw(target)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+//! In-process stand-in for SteelSeries Engine
+//!
+//! [`MockEngine`] accepts the requests of the GameSense API on a local port, answers them like the
+//! Engine does and records them. This exercises the full registration/bind/update code path on
+//! machines without SteelSeries GG, e.g. on Linux CI:
+//!
+//! ```ignore
+//! let engine = MockEngine::start()?;
+//! let mut api = GameSenseAPI::with_config("TEST".to_string(), engine.config());
+//! api.start()?;
+//! assert_eq!(engine.requests()[0].endpoint, "game_metadata");
+//! ```
+//!
+//! Only available with the `mock` feature.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::config::Config;
+
+/// A request received by the [`MockEngine`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    /// The endpoint without the leading slash, e.g. `game_event`
+    pub endpoint: String,
+    /// The JSON body, `Null` if the body was not valid JSON
+    pub body: serde_json::Value,
+}
+
+/// Local HTTP server answering like SteelSeries Engine
+///
+/// Every request is answered with `200 {}` and recorded. The server stops when dropped.
+pub struct MockEngine {
+    address: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl MockEngine {
+    /// Start the server on a free local port
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no local port could be bound.
+    pub fn start() -> std::io::Result<MockEngine> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let address = listener.local_addr()?.to_string();
+        let requests = Arc::new(Mutex::new(vec![]));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let recorded = Arc::clone(&requests);
+        let keep_running = Arc::clone(&running);
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if !keep_running.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let _ = handle(stream, &recorded);
+            }
+        });
+
+        Ok(MockEngine {
+            address,
+            requests,
+            running,
+        })
+    }
+
+    /// The address (`host:port`) of the server
+    #[must_use]
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// A default [`Config`] pointing to this server
+    #[must_use]
+    pub fn config(&self) -> Config {
+        Config {
+            address: Some(self.address.clone()),
+            ..Config::default()
+        }
+    }
+
+    /// All requests received so far, oldest first
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server thread panicked while recording a request.
+    #[must_use]
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+impl Drop for MockEngine {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        // wake up the server thread so it notices the shutdown
+        let _ = TcpStream::connect(&self.address);
+    }
+}
+
+// Helper which reads a single HTTP request, records it and answers it. The request is recorded
+// before the response is written, so it is visible as soon as the client's call returned.
+fn handle(stream: TcpStream, recorded: &Mutex<Vec<RecordedRequest>>) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let endpoint = request_line
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    recorded.lock().unwrap().push(RecordedRequest {
+        endpoint,
+        body: serde_json::from_slice(&body).unwrap_or_default(),
+    });
+
+    reader.get_mut().write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+    )
+}
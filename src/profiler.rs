@@ -0,0 +1,145 @@
+//! Render times of widgets
+//!
+//! [`Profiler`] measures how long every widget takes to draw, so it's easy to find the widget
+//! which makes a dashboard miss its frame rate. Draw through the profiler instead of drawing
+//! directly, and print the report from time to time:
+//!
+//! ```ignore
+//! let mut profiler = Profiler::new().budget(Duration::from_millis(5));
+//! while let Some(display) = api.display_apex_mut() {
+//!     profiler.draw("cpu", &cpu_ring, display)?;
+//!     profiler.draw("log", &log_view, display)?;
+//!     api.update_displays()?;
+//! }
+//! println!("{}", profiler.report());
+//! ```
+
+use std::{
+    fmt::Write,
+    time::{Duration, Instant},
+};
+
+use embedded_graphics::prelude::*;
+
+// Render times of a single widget
+#[derive(Default)]
+struct Times {
+    frames: u64,
+    total: Duration,
+    max: Duration,
+    over_budget: u64,
+}
+
+/// Collects the render times of named widgets
+#[derive(Default)]
+pub struct Profiler {
+    budget: Option<Duration>,
+    widgets: Vec<(String, Times)>,
+}
+
+impl Profiler {
+    /// Create a profiler without a budget
+    #[must_use]
+    pub fn new() -> Profiler {
+        Profiler::default()
+    }
+
+    /// Count the frames in which a widget takes longer than `budget` to draw. The first overrun of
+    /// every widget is reported with a warning, later ones show up in the report.
+    #[must_use]
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Draw a widget and record its render time under `name`
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the draw target.
+    pub fn draw<T, D>(
+        &mut self,
+        name: &str,
+        drawable: &T,
+        target: &mut D,
+    ) -> Result<T::Output, D::Error>
+    where
+        T: Drawable,
+        D: DrawTarget<Color = T::Color>,
+    {
+        self.measure(name, || drawable.draw(target))
+    }
+
+    /// Run any rendering code, e.g. a function drawing several primitives, and record its
+    /// duration under `name`
+    pub fn measure<R>(&mut self, name: &str, render: impl FnOnce() -> R) -> R {
+        let started = Instant::now();
+        let result = render();
+        self.record(name, started.elapsed());
+        result
+    }
+
+    /// Record a render time measured elsewhere
+    pub fn record(&mut self, name: &str, elapsed: Duration) {
+        let position = self.widgets.iter().position(|(widget, _)| widget == name);
+        let index = position.unwrap_or_else(|| {
+            self.widgets.push((name.to_string(), Times::default()));
+            self.widgets.len() - 1
+        });
+        let times = &mut self.widgets[index].1;
+        times.frames += 1;
+        times.total += elapsed;
+        times.max = times.max.max(elapsed);
+        if let Some(budget) = self.budget
+            && elapsed > budget
+        {
+            times.over_budget += 1;
+            if times.over_budget == 1 {
+                println!(
+                    "Warning: Widget {name} took {elapsed:?} to draw, the budget is {budget:?}"
+                );
+            }
+        }
+    }
+
+    /// A table of the average and maximum render time of every widget, slowest first
+    #[must_use]
+    pub fn report(&self) -> String {
+        let mut widgets: Vec<&(String, Times)> = self.widgets.iter().collect();
+        widgets.sort_by_key(|(_, times)| std::cmp::Reverse(average(times)));
+        let width = widgets
+            .iter()
+            .map(|(name, _)| name.len())
+            .max()
+            .unwrap_or(0)
+            .max(6);
+        let mut report = format!(
+            "{:<width$} {:>8} {:>10} {:>10} {:>6}\n",
+            "Widget", "Frames", "Average", "Max", "Over"
+        );
+        for (name, times) in widgets {
+            let _ = writeln!(
+                report,
+                "{name:<width$} {:>8} {:>10} {:>10} {:>6}",
+                times.frames,
+                format!("{:.2?}", average(times)),
+                format!("{:.2?}", times.max),
+                times.over_budget
+            );
+        }
+        report
+    }
+
+    /// Forget all recorded times
+    pub fn reset(&mut self) {
+        self.widgets.clear();
+    }
+}
+
+// Helper which computes the average render time of a widget
+fn average(times: &Times) -> Duration {
+    u32::try_from(times.frames)
+        .ok()
+        .filter(|frames| *frames > 0)
+        .map_or(Duration::ZERO, |frames| times.total / frames)
+}
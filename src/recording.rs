@@ -0,0 +1,349 @@
+//! Compact on-disk recordings of sessions
+//!
+//! [`FrameRecorder`] streams every frame into a file, [`read_recording`] loads it again, e.g. to
+//! replay it or export parts of it with the `capture` feature. Rows which didn't change since the
+//! previous frame or repeat the row above are stored as runs, so hour-long recordings of mostly
+//! static dashboards stay small.
+//!
+//! # Format (version 1)
+//!
+//! All numbers are little-endian.
+//!
+//! | Bytes | Content                                                  |
+//! |-------|----------------------------------------------------------|
+//! | 4     | Magic `SSRC`                                             |
+//! | 1     | Format version, `1`                                      |
+//! | 2     | Width of the display in pixels                           |
+//! | 2     | Height of the display in pixels                          |
+//!
+//! The header is followed by the frames until the end of the file. Every frame starts with the
+//! time since the previous frame (the start of the recording for the first one) in milliseconds as
+//! `u32`, followed by runs describing all rows of the frame from top to bottom:
+//!
+//! | Tag    | Followed by                  | Meaning                                               |
+//! |--------|------------------------------|-------------------------------------------------------|
+//! | `0x00` | count (`u8`)                 | `count` rows unchanged since the previous frame       |
+//! | `0x01` | count (`u8`)                 | `count` copies of the row above (off for the top row) |
+//! | `0x02` | `width / 8` bytes            | One row of packed pixels, MSB first                   |
+//!
+//! Before the first frame, all pixels are off. Files which end within a frame (including its
+//! time) are truncated and rejected.
+//!
+//! Only available with the `recording` feature.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Result, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
+
+use embedded_graphics::prelude::*;
+
+use crate::display::{SteelSeriesDisplay, SteelSeriesLCDType};
+
+const MAGIC: &[u8; 4] = b"SSRC";
+const VERSION: u8 = 1;
+const UNCHANGED: u8 = 0x00;
+const REPEATED: u8 = 0x01;
+const LITERAL: u8 = 0x02;
+
+/// Streams the frames of one display type into a recording file
+pub struct FrameRecorder {
+    writer: BufWriter<File>,
+    previous: SteelSeriesDisplay,
+    last_frame: Instant,
+}
+
+impl FrameRecorder {
+    /// Create (or truncate) the recording file for displays of `lcd_type`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be created.
+    pub fn create<P: AsRef<Path>>(path: P, lcd_type: SteelSeriesLCDType) -> Result<FrameRecorder> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let size = lcd_type.dimensions();
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        for dimension in [size.width, size.height] {
+            writer.write_all(&u16::try_from(dimension).unwrap_or(u16::MAX).to_le_bytes())?;
+        }
+        Ok(FrameRecorder {
+            writer,
+            previous: SteelSeriesDisplay::new(lcd_type),
+            last_frame: Instant::now(),
+        })
+    }
+
+    /// Append the current content of a display. Displays of another type than the recording are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the frame could not be written.
+    pub fn record(&mut self, display: &SteelSeriesDisplay) -> Result<()> {
+        if display.lcd_type() != self.previous.lcd_type() {
+            println!(
+                "Warning: Ignoring frame of a {:?} display in a {:?} recording",
+                display.lcd_type(),
+                self.previous.lcd_type()
+            );
+            return Ok(());
+        }
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_frame);
+        self.last_frame = now;
+        self.write_frame(display, elapsed)
+    }
+
+    // Helper which encodes a frame shown `elapsed` after the previous one
+    fn write_frame(&mut self, display: &SteelSeriesDisplay, elapsed: Duration) -> Result<()> {
+        let elapsed = u32::try_from(elapsed.as_millis()).unwrap_or(u32::MAX);
+        self.writer.write_all(&elapsed.to_le_bytes())?;
+
+        let width = row_bytes(display);
+        let rows: Vec<&[u8]> = display.framebuffer.chunks(width).collect();
+        let previous: Vec<&[u8]> = self.previous.framebuffer.chunks(width).collect();
+        let blank = vec![0; width];
+        let mut row = 0;
+        while row < rows.len() {
+            let unchanged = run(row, rows.len(), |index| rows[index] == previous[index]);
+            let repeated = run(row, rows.len(), |index| {
+                rows[index] == index.checked_sub(1).map_or(&blank[..], |above| rows[above])
+            });
+            if unchanged > 0 && unchanged >= repeated {
+                self.writer.write_all(&[UNCHANGED, unchanged])?;
+                row += usize::from(unchanged);
+            } else if repeated > 0 {
+                self.writer.write_all(&[REPEATED, repeated])?;
+                row += usize::from(repeated);
+            } else {
+                self.writer.write_all(&[LITERAL])?;
+                self.writer.write_all(rows[row])?;
+                row += 1;
+            }
+        }
+        self.previous
+            .framebuffer
+            .copy_from_slice(&display.framebuffer);
+        Ok(())
+    }
+
+    /// Write all buffered frames to the file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be written.
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Load all frames of a recording, each with the time since the previous frame
+///
+/// # Errors
+///
+/// Returns an error if the file could not be read, is no recording of a supported display size
+/// or is truncated.
+pub fn read_recording<P: AsRef<Path>>(path: P) -> Result<Vec<(Duration, SteelSeriesDisplay)>> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut header = [0; 9];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != MAGIC || header[4] != VERSION {
+        return Err(invalid("not a recording of version 1"));
+    }
+    let size = Size::new(
+        u32::from(u16::from_le_bytes([header[5], header[6]])),
+        u32::from(u16::from_le_bytes([header[7], header[8]])),
+    );
+    let lcd_type = *SteelSeriesLCDType::all()
+        .iter()
+        .find(|lcd_type| lcd_type.dimensions() == size)
+        .ok_or_else(|| invalid("unsupported display size"))?;
+
+    let mut frames = vec![];
+    let mut current = SteelSeriesDisplay::new(lcd_type);
+    let width = row_bytes(&current);
+    let height = current.framebuffer.len() / width;
+    let mut elapsed = [0; 4];
+    // the file may only end between frames
+    while !reader.fill_buf()?.is_empty() {
+        reader.read_exact(&mut elapsed)?;
+        let previous = current.framebuffer.clone();
+        let mut row = 0;
+        while row < height {
+            let mut tag = [0; 2];
+            reader.read_exact(&mut tag[..1])?;
+            let range = row * width..(row + 1) * width;
+            match tag[0] {
+                LITERAL => {
+                    reader.read_exact(&mut current.framebuffer[range])?;
+                    row += 1;
+                }
+                UNCHANGED | REPEATED => {
+                    reader.read_exact(&mut tag[1..])?;
+                    let count = usize::from(tag[1]);
+                    if count == 0 || row + count > height {
+                        return Err(invalid("run exceeds the frame"));
+                    }
+                    for index in row..row + count {
+                        let target = index * width..(index + 1) * width;
+                        if tag[0] == UNCHANGED {
+                            current.framebuffer[target.clone()].copy_from_slice(&previous[target]);
+                        } else if index == 0 {
+                            current.framebuffer[target].fill(0);
+                        } else {
+                            current
+                                .framebuffer
+                                .copy_within((index - 1) * width..index * width, target.start);
+                        }
+                    }
+                    row += count;
+                }
+                _ => return Err(invalid("unknown run tag")),
+            }
+        }
+        frames.push((
+            Duration::from_millis(u64::from(u32::from_le_bytes(elapsed))),
+            current.clone(),
+        ));
+    }
+    Ok(frames)
+}
+
+// Helper which returns the number of bytes of a row of the framebuffer
+fn row_bytes(display: &SteelSeriesDisplay) -> usize {
+    display.size().width as usize / 8
+}
+
+// Helper which counts the rows from `start` on (at most 255) which satisfy `matches`
+fn run(start: usize, end: usize, matches: impl Fn(usize) -> bool) -> u8 {
+    let count = (start..end.min(start + 255))
+        .take_while(|&index| matches(index))
+        .count();
+    u8::try_from(count).unwrap_or(u8::MAX)
+}
+
+// Helper which creates the error for malformed recordings
+fn invalid(message: &str) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!("Invalid recording: {message}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_graphics::pixelcolor::BinaryColor;
+
+    use super::*;
+
+    // Helper which builds a path in the temp directory, unique per test
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("ssrc-{}-{name}.ssrc", std::process::id()))
+    }
+
+    // Frames covering all kinds of runs: blank rows, literal rows followed by repeated rows, a
+    // frame identical to the previous one, a partially changed frame and a blank frame after a
+    // full one, where the top row repeats the blank row above the screen
+    fn frames() -> Vec<(Duration, SteelSeriesDisplay)> {
+        let blank = SteelSeriesDisplay::new(SteelSeriesLCDType::Apex);
+        let mut stripes = blank.clone();
+        for (index, byte) in stripes.framebuffer.iter_mut().enumerate() {
+            *byte = match index / 16 {
+                0 => u8::try_from(index).unwrap(),
+                5..=9 => 0xFF,
+                20 => 0xA5,
+                _ => 0,
+            };
+        }
+        let mut changed = stripes.clone();
+        let _ = Pixel(Point::new(64, 30), BinaryColor::On).draw(&mut changed);
+        let _ = Pixel(Point::new(0, 39), BinaryColor::On).draw(&mut changed);
+        let mut full = blank.clone();
+        full.framebuffer.fill(0xFF);
+        vec![
+            (Duration::from_millis(0), blank.clone()),
+            (Duration::from_millis(16), stripes.clone()),
+            (Duration::from_millis(33), stripes),
+            (Duration::from_secs(1), changed),
+            (Duration::from_secs(70), full),
+            (Duration::from_millis(5), blank),
+        ]
+    }
+
+    // Helper which records the frames into a file and returns its size
+    fn record(path: &Path, frames: &[(Duration, SteelSeriesDisplay)]) -> u64 {
+        let mut recorder = FrameRecorder::create(path, SteelSeriesLCDType::Apex).unwrap();
+        for (elapsed, display) in frames {
+            recorder.write_frame(display, *elapsed).unwrap();
+        }
+        recorder.flush().unwrap();
+        std::fs::metadata(path).unwrap().len()
+    }
+
+    #[test]
+    fn round_trip() {
+        let path = temp_path("round-trip");
+        let frames = frames();
+        record(&path, &frames);
+        let read = read_recording(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(read.len(), frames.len());
+        for ((elapsed, display), (read_elapsed, read_display)) in frames.iter().zip(&read) {
+            assert_eq!(elapsed, read_elapsed);
+            assert_eq!(read_display.lcd_type(), SteelSeriesLCDType::Apex);
+            assert_eq!(display.framebuffer, read_display.framebuffer);
+        }
+    }
+
+    #[test]
+    fn runs_keep_recordings_small() {
+        let path = temp_path("small");
+        let frames = frames();
+        let blank = record(&path, &frames[..1]);
+        let stripes = record(&path, &frames[..2]);
+        let repeated = record(&path, &frames[..3]);
+        let _ = std::fs::remove_file(&path);
+
+        // header, elapsed time and a single run of 40 rows
+        assert_eq!(blank, 9 + 4 + 2);
+        // three literal rows, the other rows are four runs
+        assert_eq!(stripes - blank, 4 + 3 * 17 + 4 * 2);
+        assert_eq!(repeated - stripes, 4 + 2);
+    }
+
+    #[test]
+    fn runs_are_split_after_255_rows() {
+        assert_eq!(run(0, 600, |_| true), 255);
+        assert_eq!(run(255, 600, |_| true), 255);
+        assert_eq!(run(510, 600, |_| true), 90);
+        assert_eq!(run(0, 600, |index| index < 3), 3);
+    }
+
+    #[test]
+    fn truncated_recordings_are_rejected() {
+        let path = temp_path("truncated");
+        record(&path, &frames());
+        let bytes = std::fs::read(&path).unwrap();
+        // the last frame is 4 bytes of time followed by a single run
+        let cuts = [bytes.len() - 1, bytes.len() - 3, bytes.len() - 5, 5];
+        let results: Vec<_> = cuts
+            .iter()
+            .map(|&cut| {
+                std::fs::write(&path, &bytes[..cut]).unwrap();
+                read_recording(&path)
+            })
+            .collect();
+        let _ = std::fs::remove_file(&path);
+
+        for (cut, result) in cuts.iter().zip(results) {
+            assert!(
+                matches!(result, Err(err) if err.kind() == ErrorKind::UnexpectedEof),
+                "cut after {cut} bytes"
+            );
+        }
+    }
+}
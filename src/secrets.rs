@@ -0,0 +1,63 @@
+//! Secret storage backed by the OS keychain
+//!
+//! API keys, TOTP secrets and credentials should not end up in plaintext config files. This module
+//! stores them in the Windows Credential Manager or the macOS Keychain instead, grouped by a
+//! service name (usually the game name of the application).
+//!
+//! Only available with the `secrets` feature.
+
+use keyring::Entry;
+pub use keyring::Error;
+
+/// Access to the secrets of a single service in the OS keychain
+pub struct Secrets {
+    service: String,
+}
+
+impl Secrets {
+    /// Create a handle for the secrets of `service`
+    #[must_use]
+    pub fn new(service: &str) -> Secrets {
+        Secrets {
+            service: service.to_string(),
+        }
+    }
+
+    fn entry(&self, key: &str) -> Result<Entry, Error> {
+        Entry::new(&self.service, key)
+    }
+
+    /// Read a secret. Returns `Ok(None)` if no secret is stored for `key`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keychain is not accessible.
+    pub fn get(&self, key: &str) -> Result<Option<String>, Error> {
+        match self.entry(key)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(Error::NoEntry) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Store a secret, replacing any existing secret for `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keychain is not accessible.
+    pub fn set(&self, key: &str, secret: &str) -> Result<(), Error> {
+        self.entry(key)?.set_password(secret)
+    }
+
+    /// Delete a secret. Deleting a secret which does not exist is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the keychain is not accessible.
+    pub fn delete(&self, key: &str) -> Result<(), Error> {
+        match self.entry(key)?.delete_credential() {
+            Ok(()) | Err(Error::NoEntry) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+}
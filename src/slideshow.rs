@@ -0,0 +1,189 @@
+//! Slideshow of the images in a folder
+//!
+//! Cycles through all images of a directory on a fixed interval. Every image is scaled down to fit
+//! the display (keeping its aspect ratio), converted to grayscale and dithered to black and white,
+//! so photos and pixel art can be shown without preparing them first.
+//!
+//! Only available with the `slideshow` feature.
+
+use std::{
+    io::Error,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+use image::{
+    GrayImage,
+    imageops::{self, BiLevel, FilterType},
+};
+
+const EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "bmp"];
+
+// a converted image, ready to be drawn
+struct Slide {
+    size: Size,
+    pixels: Vec<BinaryColor>,
+}
+
+/// Cycles through the images of a directory
+pub struct Slideshow {
+    paths: Vec<PathBuf>,
+    index: usize,
+    interval: Duration,
+    shuffle: bool,
+    seed: u64,
+    shown_since: Instant,
+    size: Size,
+    slide: Option<Slide>,
+}
+
+impl Slideshow {
+    /// Create a slideshow of all images (png, jpg, gif, bmp) in `directory`
+    /// # Arguments
+    ///
+    /// * `directory` - The directory to read the images from (not recursive)
+    /// * `size` - The size of the target display, e.g. `SteelSeriesLCDType::Apex.dimensions()`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory could not be read.
+    pub fn new<P: AsRef<Path>>(directory: P, size: Size) -> Result<Slideshow, Error> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(directory)?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.extension()
+                    .and_then(|extension| extension.to_str())
+                    .is_some_and(|extension| {
+                        EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+                    })
+            })
+            .collect();
+        paths.sort();
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(1, |time| time.as_secs())
+            | 1;
+        Ok(Slideshow {
+            paths,
+            index: 0,
+            interval: Duration::from_secs(10),
+            shuffle: false,
+            seed,
+            shown_since: Instant::now(),
+            size,
+            slide: None,
+        })
+    }
+
+    /// Set how long each image is shown (defaults to 10 seconds)
+    #[must_use]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Show the images in random order instead of sorted by file name
+    #[must_use]
+    pub fn shuffle(mut self, shuffle: bool) -> Self {
+        self.shuffle = shuffle;
+        self
+    }
+
+    /// Number of images in the slideshow
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Whether the directory contained no images
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Path of the image currently shown
+    #[must_use]
+    pub fn current(&self) -> Option<&Path> {
+        self.paths.get(self.index).map(PathBuf::as_path)
+    }
+
+    /// Advance to the next image if the interval has elapsed. Returns true if the image changed and
+    /// the display needs to be redrawn.
+    pub fn tick(&mut self) -> bool {
+        if self.shown_since.elapsed() < self.interval || self.paths.len() < 2 {
+            return false;
+        }
+        self.advance();
+        true
+    }
+
+    /// Advance to the next image immediately
+    pub fn advance(&mut self) {
+        if self.paths.is_empty() {
+            return;
+        }
+        self.index = if self.shuffle && self.paths.len() > 1 {
+            // xorshift, good enough to pick the next slide
+            self.seed ^= self.seed << 13;
+            self.seed ^= self.seed >> 7;
+            self.seed ^= self.seed << 17;
+            let offset = usize::try_from(self.seed % (self.paths.len() as u64 - 1)).unwrap_or(0);
+            (self.index + 1 + offset) % self.paths.len()
+        } else {
+            (self.index + 1) % self.paths.len()
+        };
+        self.slide = None;
+        self.shown_since = Instant::now();
+    }
+
+    // load and convert the current image, skipping images which can't be decoded
+    fn load(&self) -> Option<Slide> {
+        let image = image::open(self.current()?).ok()?.to_luma8();
+        let mut image = fit(&image, self.size);
+        imageops::dither(&mut image, &BiLevel);
+        Some(Slide {
+            size: Size::new(image.width(), image.height()),
+            pixels: image
+                .pixels()
+                .map(|pixel| BinaryColor::from(pixel.0[0] > 127))
+                .collect(),
+        })
+    }
+
+    /// Draw the current image centered on `target`. Images are only decoded once per slide.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the draw target.
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+    {
+        if self.slide.is_none() {
+            self.slide = self.load();
+        }
+        target.clear(BinaryColor::Off)?;
+        let Some(slide) = &self.slide else {
+            return Ok(());
+        };
+        let area = Rectangle::with_center(target.bounding_box().center(), slide.size);
+        target.fill_contiguous(&area, slide.pixels.iter().copied())
+    }
+}
+
+// Helper which scales an image down to fit into `size`, keeping its aspect ratio
+fn fit(image: &GrayImage, size: Size) -> GrayImage {
+    let (width, height) = image.dimensions();
+    if width <= size.width && height <= size.height {
+        return image.clone();
+    }
+    let (width, height) =
+        if u64::from(width) * u64::from(size.height) > u64::from(height) * u64::from(size.width) {
+            (size.width, (height * size.width / width).max(1))
+        } else {
+            ((width * size.height / height).max(1), size.height)
+        };
+    imageops::resize(image, width, height, FilterType::Triangle)
+}
@@ -0,0 +1,287 @@
+use std::{
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+/// Statistics of the current session of a [`GameSenseAPI`](crate::GameSenseAPI)
+///
+/// Get them with `GameSenseAPI::stats()`, e.g. to tune the update rate or to show them with
+/// [`StatsView`](crate::widgets::StatsView).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SessionStats {
+    /// Time since the API was created
+    pub uptime: Duration,
+    /// Frames which were accepted by the Engine
+    pub frames_sent: u64,
+    /// Bytes of all request bodies sent to the Engine
+    pub bytes_sent: u64,
+    /// Size in bytes of the largest request body sent to the Engine
+    pub largest_payload: u64,
+    /// Calls of `reconnect()`
+    pub reconnects: u64,
+    /// Frames which could not be sent or were rejected by the Engine
+    pub dropped_frames: u64,
+    /// Frames which were skipped while the Engine was slow
+    pub skipped_frames: u64,
+    /// Smoothed duration of the requests to the Engine
+    pub latency: Duration,
+    /// Whether the frame rate is currently reduced because the Engine is slow
+    pub degraded: bool,
+}
+
+impl SessionStats {
+    /// Average number of frames sent per second
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn frame_rate(&self) -> f64 {
+        let seconds = self.uptime.as_secs_f64();
+        if seconds > 0.0 {
+            self.frames_sent as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+}
+
+/// What the displays of a [`GameSenseAPI`](crate::GameSenseAPI) currently show
+///
+/// Get it with `GameSenseAPI::activity()`, e.g. to publish a busy/away status in other tools.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Activity {
+    /// Event of the last frame accepted by the Engine, `None` before the first frame
+    pub event: Option<String>,
+    /// Time since the last frame accepted by the Engine
+    pub since_last_frame: Option<Duration>,
+}
+
+impl Activity {
+    /// Whether a frame was sent within the last `within`, i.e. the content is actively updated
+    #[must_use]
+    pub fn is_active(&self, within: Duration) -> bool {
+        self.since_last_frame
+            .is_some_and(|elapsed| elapsed <= within)
+    }
+}
+
+// Counters shared by all requests of an API instance
+pub(crate) struct Counters {
+    started: Instant,
+    frames_sent: AtomicU64,
+    bytes_sent: AtomicU64,
+    largest_payload: AtomicU64,
+    reconnects: AtomicU64,
+    dropped_frames: AtomicU64,
+    skipped_frames: AtomicU64,
+    latency_us: AtomicU64,
+    degraded: AtomicBool,
+    last_frame: Mutex<Option<Instant>>,
+    last_event: Mutex<Option<String>>,
+}
+
+impl Counters {
+    pub(crate) fn new() -> Counters {
+        Counters {
+            started: Instant::now(),
+            frames_sent: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            largest_payload: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+            dropped_frames: AtomicU64::new(0),
+            skipped_frames: AtomicU64::new(0),
+            latency_us: AtomicU64::new(0),
+            degraded: AtomicBool::new(false),
+            last_frame: Mutex::new(None),
+            last_event: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn sent_bytes(&self, bytes: usize) {
+        let bytes = u64::try_from(bytes).unwrap_or(u64::MAX);
+        self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+        self.largest_payload.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn sent_frame(&self, event: &str, accepted: bool) {
+        self.sent_frame_at(event, accepted, Instant::now());
+    }
+
+    fn sent_frame_at(&self, event: &str, accepted: bool, now: Instant) {
+        if accepted {
+            self.frames_sent.fetch_add(1, Ordering::Relaxed);
+            *self.last_frame.lock().unwrap() = Some(now);
+            *self.last_event.lock().unwrap() = Some(event.to_string());
+        } else {
+            self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    // Record the duration of a request. The API is degraded while the smoothed latency exceeds
+    // the threshold and recovers once it dropped below half of it, a zero threshold disables it.
+    // Returns the new state if it changed.
+    pub(crate) fn measured(&self, latency: Duration, threshold: Duration) -> Option<bool> {
+        let sample = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let previous = self.latency_us.load(Ordering::Relaxed);
+        let smoothed = if previous == 0 {
+            sample
+        } else {
+            previous / 4 * 3 + sample / 4
+        };
+        self.latency_us.store(smoothed, Ordering::Relaxed);
+
+        let threshold = u64::try_from(threshold.as_micros()).unwrap_or(u64::MAX);
+        let degraded = if threshold == 0 || smoothed < threshold / 2 {
+            false
+        } else if smoothed > threshold {
+            true
+        } else {
+            return None;
+        };
+        (self.degraded.swap(degraded, Ordering::Relaxed) != degraded).then_some(degraded)
+    }
+
+    // Time until the next frame may be sent while degraded
+    pub(crate) fn next_frame_in(&self, interval: Duration) -> Duration {
+        self.next_frame_in_at(interval, Instant::now())
+    }
+
+    fn next_frame_in_at(&self, interval: Duration, now: Instant) -> Duration {
+        self.last_frame
+            .lock()
+            .unwrap()
+            .map_or(Duration::ZERO, |last| {
+                interval.saturating_sub(now.saturating_duration_since(last))
+            })
+    }
+
+    // Whether a frame should be skipped: while degraded, frames are only sent every `interval`
+    pub(crate) fn throttle(&self, interval: Duration) -> bool {
+        self.throttle_at(interval, Instant::now())
+    }
+
+    fn throttle_at(&self, interval: Duration, now: Instant) -> bool {
+        if !self.degraded.load(Ordering::Relaxed) {
+            return false;
+        }
+        let skip = self
+            .last_frame
+            .lock()
+            .unwrap()
+            .is_some_and(|last| now.saturating_duration_since(last) < interval);
+        if skip {
+            self.skipped_frames.fetch_add(1, Ordering::Relaxed);
+        }
+        skip
+    }
+
+    pub(crate) fn reconnected(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn activity(&self) -> Activity {
+        Activity {
+            event: self.last_event.lock().unwrap().clone(),
+            since_last_frame: self.last_frame.lock().unwrap().map(|last| last.elapsed()),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> SessionStats {
+        SessionStats {
+            uptime: self.started.elapsed(),
+            frames_sent: self.frames_sent.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+            largest_payload: self.largest_payload.load(Ordering::Relaxed),
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            dropped_frames: self.dropped_frames.load(Ordering::Relaxed),
+            skipped_frames: self.skipped_frames.load(Ordering::Relaxed),
+            latency: Duration::from_micros(self.latency_us.load(Ordering::Relaxed)),
+            degraded: self.degraded.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_millis(100);
+    const INTERVAL: Duration = Duration::from_secs(1);
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn degraded_above_the_threshold() {
+        let counters = Counters::new();
+        // between half of the threshold and the threshold nothing changes
+        assert_eq!(counters.measured(ms(70), THRESHOLD), None);
+        assert!(!counters.snapshot().degraded);
+        // smoothed to 102.5ms
+        assert_eq!(counters.measured(ms(200), THRESHOLD), Some(true));
+        assert!(counters.snapshot().degraded);
+        assert_eq!(counters.measured(ms(200), THRESHOLD), None);
+    }
+
+    #[test]
+    fn recovers_below_half_of_the_threshold() {
+        let counters = Counters::new();
+        assert_eq!(counters.measured(ms(200), THRESHOLD), Some(true));
+        let mut changes = vec![];
+        while counters.snapshot().latency >= THRESHOLD / 2 {
+            let degraded = counters.snapshot().degraded;
+            assert!(degraded, "recovered above half of the threshold");
+            changes.extend(counters.measured(ms(10), THRESHOLD));
+        }
+        assert_eq!(changes, [false]);
+        assert!(!counters.snapshot().degraded);
+    }
+
+    #[test]
+    fn zero_threshold_disables_degradation() {
+        let counters = Counters::new();
+        assert_eq!(counters.measured(ms(10_000), Duration::ZERO), None);
+        assert!(!counters.snapshot().degraded);
+        assert!(!counters.throttle(INTERVAL));
+    }
+
+    #[test]
+    fn frames_are_throttled_while_degraded() {
+        let counters = Counters::new();
+        let start = Instant::now();
+        // the first frame is always sent
+        counters.measured(ms(200), THRESHOLD);
+        assert!(!counters.throttle_at(INTERVAL, start));
+        assert_eq!(counters.next_frame_in_at(INTERVAL, start), Duration::ZERO);
+
+        counters.sent_frame_at("UPDATE", true, start);
+        assert!(counters.throttle_at(INTERVAL, start + ms(500)));
+        assert_eq!(
+            counters.next_frame_in_at(INTERVAL, start + ms(300)),
+            ms(700)
+        );
+        assert!(!counters.throttle_at(INTERVAL, start + INTERVAL));
+        assert_eq!(
+            counters.next_frame_in_at(INTERVAL, start + ms(2_000)),
+            Duration::ZERO
+        );
+        assert_eq!(counters.snapshot().skipped_frames, 1);
+
+        // frames are sent as they come once the Engine recovered
+        while counters.measured(ms(1), THRESHOLD).is_none() {}
+        assert!(!counters.throttle_at(INTERVAL, start + ms(1)));
+    }
+
+    #[test]
+    fn rejected_frames_do_not_reset_the_interval() {
+        let counters = Counters::new();
+        let start = Instant::now();
+        counters.measured(ms(200), THRESHOLD);
+        counters.sent_frame_at("UPDATE", true, start);
+        counters.sent_frame_at("UPDATE", false, start + ms(900));
+        assert!(!counters.throttle_at(INTERVAL, start + INTERVAL));
+        assert_eq!(counters.snapshot().dropped_frames, 1);
+    }
+}
@@ -0,0 +1,136 @@
+//! Persistence of dashboard state across restarts
+//!
+//! Widgets and data providers keep counters, streaks or history in a [`Storage`] under a key of
+//! their own, so a restarted daemon continues where it stopped. [`JsonFileStorage`] keeps all
+//! values in a single JSON file; implement the trait to store them elsewhere.
+//!
+//! ```ignore
+//! let mut storage = JsonFileStorage::open("dashboard-state.json")?;
+//! let mut streak: u32 = storage.get_as("pomodoro.streak").unwrap_or(0);
+//! streak += 1;
+//! storage.set("pomodoro.streak", streak.into())?;
+//! ```
+
+use std::{
+    collections::HashMap,
+    fs,
+    hash::BuildHasher,
+    io::{Error, ErrorKind, Result},
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Key-value store for JSON values
+pub trait Storage {
+    /// Read the value stored for `key`, `None` if there is none
+    fn get(&self, key: &str) -> Option<serde_json::Value>;
+
+    /// Store a value, replacing any existing value for `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value could not be persisted.
+    fn set(&mut self, key: &str, value: serde_json::Value) -> Result<()>;
+
+    /// Delete the value for `key`. Deleting a value which does not exist is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the change could not be persisted.
+    fn delete(&mut self, key: &str) -> Result<()>;
+
+    /// Read the value stored for `key` as `T`, `None` if there is none or it has another type
+    fn get_as<T: DeserializeOwned>(&self, key: &str) -> Option<T>
+    where
+        Self: Sized,
+    {
+        serde_json::from_value(self.get(key)?).ok()
+    }
+
+    /// Store any serializable value for `key`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value could not be serialized or persisted.
+    fn set_as<T: Serialize>(&mut self, key: &str, value: &T) -> Result<()>
+    where
+        Self: Sized,
+    {
+        let value =
+            serde_json::to_value(value).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+        self.set(key, value)
+    }
+}
+
+/// Values kept in memory only, e.g. for tests or to opt out of persistence
+impl<S: BuildHasher> Storage for HashMap<String, serde_json::Value, S> {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        HashMap::get(self, key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: serde_json::Value) -> Result<()> {
+        self.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        self.remove(key);
+        Ok(())
+    }
+}
+
+/// [`Storage`] in a JSON file holding an object with all keys
+///
+/// Every change rewrites the file. It is written to a temporary file first and then renamed, so a
+/// crash while saving doesn't lose the previous state.
+pub struct JsonFileStorage {
+    path: PathBuf,
+    values: serde_json::Map<String, serde_json::Value>,
+}
+
+impl JsonFileStorage {
+    /// Open the storage in `path`, starting empty if the file doesn't exist yet
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but could not be read or holds no JSON object.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<JsonFileStorage> {
+        let path = path.as_ref().to_path_buf();
+        let values = match fs::read_to_string(&path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|err| Error::new(ErrorKind::InvalidData, err))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => serde_json::Map::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(JsonFileStorage { path, values })
+    }
+
+    // Helper which writes all values to the file
+    fn save(&self) -> Result<()> {
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let content = serde_json::to_string_pretty(&self.values)
+            .expect("JSON values are always serializable");
+        fs::write(&temporary, content)?;
+        fs::rename(&temporary, &self.path)
+    }
+}
+
+impl Storage for JsonFileStorage {
+    fn get(&self, key: &str) -> Option<serde_json::Value> {
+        self.values.get(key).cloned()
+    }
+
+    fn set(&mut self, key: &str, value: serde_json::Value) -> Result<()> {
+        self.values.insert(key.to_string(), value);
+        self.save()
+    }
+
+    fn delete(&mut self, key: &str) -> Result<()> {
+        if self.values.remove(key).is_some() {
+            self.save()?;
+        }
+        Ok(())
+    }
+}
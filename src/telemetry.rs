@@ -0,0 +1,219 @@
+//! Dashboard for sim-racing telemetry
+//!
+//! [`TelemetryReceiver`] listens for the UDP telemetry many racing games send to a local port and
+//! [`RaceDashboard`] renders the latest [`Telemetry`] as a screen with the gear, an RPM bar, the
+//! speed and the fuel level.
+//!
+//! The packets are expected in the Codemasters "extradata=3" format, which is sent by Dirt Rally
+//! 2.0, Dirt 4 and older F1 titles (and emulated by several other games and telemetry tools).
+//! Enable it in the `hardware_settings_config.xml` of the game, e.g.
+//! `<udp enabled="true" extradata="3" ip="127.0.0.1" port="20777" delay="1" />`.
+//!
+//! ```ignore
+//! let mut receiver = TelemetryReceiver::bind(DEFAULT_ADDRESS)?;
+//! let mut telemetry = Telemetry::default();
+//! loop {
+//!     if let Some(latest) = receiver.poll()? {
+//!         telemetry = latest;
+//!     }
+//!     let area = display.bounding_box();
+//!     RaceDashboard::new(&telemetry, area).draw(&mut display)?;
+//! }
+//! ```
+//!
+//! Only available with the `telemetry` feature.
+
+use std::{
+    io::{Error, ErrorKind},
+    net::{ToSocketAddrs, UdpSocket},
+};
+
+use embedded_graphics::{
+    mono_font::{
+        MonoTextStyle,
+        iso_8859_1::{FONT_6X10, FONT_10X20},
+    },
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+/// Default address the games send their telemetry to
+pub const DEFAULT_ADDRESS: &str = "127.0.0.1:20777";
+
+// Positions of the values in the packet, counted in little-endian f32s
+const SPEED: usize = 7;
+const GEAR: usize = 33;
+const RPM: usize = 37;
+const FUEL: usize = 45;
+const FUEL_CAPACITY: usize = 46;
+const MAX_RPM: usize = 63;
+
+/// Snapshot of the state of the car
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Telemetry {
+    /// Speed in km/h
+    pub speed_kmh: f32,
+    /// Current gear, 0 is neutral and -1 reverse
+    pub gear: i8,
+    /// Engine speed in revolutions per minute
+    pub rpm: f32,
+    /// Engine speed at which the limiter kicks in, 0 if the game doesn't send it
+    pub max_rpm: f32,
+    /// Fuel left in the tank in liters
+    pub fuel: f32,
+    /// Size of the tank in liters, 0 if the game doesn't send it
+    pub fuel_capacity: f32,
+}
+
+impl Telemetry {
+    /// Parse a packet in the "extradata=3" format, `None` if it is too short
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn parse(packet: &[u8]) -> Option<Telemetry> {
+        let value = |index: usize| {
+            let bytes = packet.get(index * 4..index * 4 + 4)?;
+            Some(f32::from_le_bytes(bytes.try_into().ok()?))
+        };
+        let gear = value(GEAR)?;
+        Some(Telemetry {
+            speed_kmh: value(SPEED)? * 3.6,
+            // Dirt sends 10 for the reverse gear, F1 sends -1
+            gear: if (0.0..10.0).contains(&gear) {
+                gear.round() as i8
+            } else {
+                -1
+            },
+            // the engine speed is sent in tens of revolutions per minute
+            rpm: value(RPM)? * 10.0,
+            max_rpm: value(MAX_RPM)? * 10.0,
+            fuel: value(FUEL)?,
+            fuel_capacity: value(FUEL_CAPACITY)?,
+        })
+    }
+
+    /// The gear as shown on the dashboard, `R`, `N` or the number
+    #[must_use]
+    pub fn gear_label(&self) -> String {
+        match self.gear {
+            i8::MIN..=-1 => "R".to_string(),
+            0 => "N".to_string(),
+            gear => gear.to_string(),
+        }
+    }
+
+    /// Fill level of the tank from 0 to 1, `None` if the capacity is unknown
+    #[must_use]
+    pub fn fuel_level(&self) -> Option<f32> {
+        (self.fuel_capacity > 0.0).then(|| (self.fuel / self.fuel_capacity).clamp(0.0, 1.0))
+    }
+}
+
+/// Receives the telemetry packets of a game
+pub struct TelemetryReceiver {
+    socket: UdpSocket,
+    buffer: Vec<u8>,
+}
+
+impl TelemetryReceiver {
+    /// Listen for packets, e.g. at [`DEFAULT_ADDRESS`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the address could not be bound.
+    pub fn bind<A: ToSocketAddrs>(address: A) -> Result<TelemetryReceiver, Error> {
+        let socket = UdpSocket::bind(address)?;
+        socket.set_nonblocking(true)?;
+        Ok(TelemetryReceiver {
+            socket,
+            buffer: vec![0; 2048],
+        })
+    }
+
+    /// The latest telemetry received since the last poll, `None` if no (valid) packet arrived.
+    /// Never blocks, older packets are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the socket failed.
+    pub fn poll(&mut self) -> Result<Option<Telemetry>, Error> {
+        let mut latest = None;
+        loop {
+            match self.socket.recv(&mut self.buffer) {
+                Ok(length) => {
+                    if let Some(telemetry) = Telemetry::parse(&self.buffer[..length]) {
+                        latest = Some(telemetry);
+                    }
+                }
+                Err(err) if err.kind() == ErrorKind::WouldBlock => return Ok(latest),
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+/// Screen layout for a [`Telemetry`]: an RPM bar at the top, the gear in a large font below and
+/// speed and fuel next to it
+pub struct RaceDashboard<'a> {
+    telemetry: &'a Telemetry,
+    area: Rectangle,
+}
+
+impl<'a> RaceDashboard<'a> {
+    /// Create a dashboard filling `area`
+    #[must_use]
+    pub fn new(telemetry: &'a Telemetry, area: Rectangle) -> RaceDashboard<'a> {
+        RaceDashboard { telemetry, area }
+    }
+}
+
+impl Drawable for RaceDashboard<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let small = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let large = MonoTextStyle::new(&FONT_10X20, BinaryColor::On);
+        let top_left = self.area.top_left;
+        let width = self.area.size.width;
+
+        // RPM bar, full at the limiter
+        let bar = Rectangle::new(top_left, Size::new(width, 6));
+        bar.into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(target)?;
+        if self.telemetry.max_rpm > 0.0 {
+            let ratio = (self.telemetry.rpm / self.telemetry.max_rpm).clamp(0.0, 1.0);
+            let filled = ((width.saturating_sub(2)) as f32 * ratio) as u32;
+            Rectangle::new(top_left + Point::new(1, 1), Size::new(filled, 4))
+                .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                .draw(target)?;
+        }
+
+        let below = top_left + Point::new(0, 8);
+        Text::with_baseline(&self.telemetry.gear_label(), below, large, Baseline::Top)
+            .draw(target)?;
+
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        let right_edge = below + Point::new(width.cast_signed() - 1, 0);
+        let speed = format!("{:.0} km/h", self.telemetry.speed_kmh);
+        Text::with_text_style(&speed, right_edge, small, right).draw(target)?;
+        let fuel = match self.telemetry.fuel_level() {
+            Some(level) => format!("Fuel {:.0}%", level * 100.0),
+            None => format!("Fuel {:.1} l", self.telemetry.fuel),
+        };
+        Text::with_text_style(&fuel, right_edge + Point::new(0, 11), small, right).draw(target)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! Text layout helpers
+//!
+//! Helpers which work with every embedded-graphics text style (`MonoTextStyle` as well as the
+//! fonts of this crate) to position text without guessing pixel offsets.
+
+use embedded_graphics::{
+    image::{Image, ImageRaw},
+    mono_font::{
+        MonoFont, MonoTextStyle,
+        iso_8859_1::{FONT_4X6, FONT_5X8, FONT_6X10, FONT_7X13, FONT_9X15, FONT_10X20},
+    },
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text, renderer::TextRenderer},
+};
+
+use crate::widgets::Describe;
+
+/// Vertical metrics of a text style
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FontMetrics {
+    /// Pixel rows from the top of a glyph down to and including the baseline row
+    pub ascent: u32,
+    /// Pixel rows below the baseline row
+    pub descent: u32,
+    /// Distance between the baselines of two consecutive lines
+    pub line_height: u32,
+}
+
+impl FontMetrics {
+    /// Get the metrics of a text style
+    #[must_use]
+    pub fn of<S: TextRenderer>(style: &S) -> FontMetrics {
+        let bounds = style
+            .measure_string("M", Point::zero(), Baseline::Alphabetic)
+            .bounding_box;
+        // the glyph box of an alphabetic baseline at y = 0 starts `ascent - 1` rows above it
+        let ascent = (1 - bounds.top_left.y).unsigned_abs();
+        FontMetrics {
+            ascent,
+            descent: bounds.size.height.saturating_sub(ascent),
+            line_height: style.line_height(),
+        }
+    }
+
+    /// Height of a single glyph box
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.ascent + self.descent
+    }
+
+    /// The y-coordinate of the baseline for text whose glyph box starts at `top`
+    #[must_use]
+    pub fn baseline_for_top(&self, top: i32) -> i32 {
+        top + self.ascent.cast_signed() - 1
+    }
+
+    /// The y-coordinate of the baseline which vertically centers a line of text in `area`
+    #[must_use]
+    pub fn baseline_centered_in(&self, area: &Rectangle) -> i32 {
+        let free = area.size.height.saturating_sub(self.height()) / 2;
+        self.baseline_for_top(area.top_left.y + free.cast_signed())
+    }
+}
+
+/// Horizontal advance in pixels of `text` rendered with `style`
+#[must_use]
+pub fn advance<S: TextRenderer>(style: &S, text: &str) -> u32 {
+    style
+        .measure_string(text, Point::zero(), Baseline::Alphabetic)
+        .next_position
+        .x
+        .unsigned_abs()
+}
+
+enum Span<'a> {
+    Text(&'a str, MonoTextStyle<'a, BinaryColor>),
+    Icon(&'a ImageRaw<'a, BinaryColor>),
+}
+
+/// A line of text made of segments with different fonts, inverted segments and inline icons
+///
+/// All segments are laid out as one flow on a shared baseline. If a width is set, the flow wraps
+/// at word boundaries. Explicit line breaks (`\n`) are honored as well.
+///
+/// ```ignore
+/// RichText::new(Point::zero())
+///     .icon(&NOTE_ICON)
+///     .text(" Artist ", small_style)
+///     .inverted("Title", bold_style)
+///     .wrap(128)
+///     .draw(&mut display)?;
+/// ```
+pub struct RichText<'a> {
+    position: Point,
+    width: Option<u32>,
+    spans: Vec<Span<'a>>,
+}
+
+impl<'a> RichText<'a> {
+    /// Create an empty rich text whose first line starts at the top-left corner `position`
+    #[must_use]
+    pub fn new(position: Point) -> RichText<'a> {
+        RichText {
+            position,
+            width: None,
+            spans: vec![],
+        }
+    }
+
+    /// Append a text segment
+    #[must_use]
+    pub fn text(mut self, text: &'a str, style: MonoTextStyle<'a, BinaryColor>) -> Self {
+        self.spans.push(Span::Text(text, style));
+        self
+    }
+
+    /// Append a text segment which is drawn inverted (dark text on a lit background)
+    #[must_use]
+    pub fn inverted(mut self, text: &'a str, style: MonoTextStyle<'a, BinaryColor>) -> Self {
+        let mut style = style;
+        style.text_color = Some(BinaryColor::Off);
+        style.background_color = Some(BinaryColor::On);
+        self.spans.push(Span::Text(text, style));
+        self
+    }
+
+    /// Append an inline icon. The bottom row of the icon sits on the baseline.
+    #[must_use]
+    pub fn icon(mut self, icon: &'a ImageRaw<'a, BinaryColor>) -> Self {
+        self.spans.push(Span::Icon(icon));
+        self
+    }
+
+    /// Wrap the flow at word boundaries so that it stays within `width` pixels
+    #[must_use]
+    pub fn wrap(mut self, width: u32) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    // ascent and line height shared by all lines, so that every segment fits
+    fn line_metrics(&self) -> (u32, u32) {
+        self.spans
+            .iter()
+            .map(|span| match span {
+                Span::Text(_, style) => {
+                    let metrics = FontMetrics::of(style);
+                    (metrics.ascent, metrics.line_height)
+                }
+                Span::Icon(icon) => (icon.size().height, icon.size().height + 1),
+            })
+            .fold((0, 0), |(ascent, line_height), (a, l)| {
+                (ascent.max(a), line_height.max(l))
+            })
+    }
+}
+
+impl Drawable for RichText<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let (ascent, line_height) = self.line_metrics();
+        let left = self.position.x;
+        let right = self.width.map(|width| left + width.cast_signed());
+        let mut cursor = Point::new(left, self.position.y + ascent.cast_signed() - 1);
+        // move to the next line if an item of the given width does not fit anymore
+        let place = |cursor: &mut Point, width: i32| {
+            if right.is_some_and(|right| cursor.x > left && cursor.x + width > right) {
+                *cursor = Point::new(left, cursor.y + line_height.cast_signed());
+            }
+        };
+
+        for span in &self.spans {
+            match span {
+                Span::Text(text, style) => {
+                    for (index, line) in text.split('\n').enumerate() {
+                        if index > 0 {
+                            cursor = Point::new(left, cursor.y + line_height.cast_signed());
+                        }
+                        for word in line.split_inclusive(' ') {
+                            place(&mut cursor, advance(style, word.trim_end()).cast_signed());
+                            cursor =
+                                Text::with_baseline(word, cursor, *style, Baseline::Alphabetic)
+                                    .draw(target)?;
+                        }
+                    }
+                }
+                Span::Icon(icon) => {
+                    let size = icon.size();
+                    place(&mut cursor, size.width.cast_signed());
+                    let top_left = Point::new(cursor.x, cursor.y + 1 - size.height.cast_signed());
+                    Image::new(*icon, top_left).draw(target)?;
+                    cursor.x += size.width.cast_signed();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Describe for RichText<'_> {
+    fn describe(&self) -> String {
+        self.spans
+            .iter()
+            .filter_map(|span| match span {
+                Span::Text(text, _) => Some(*text),
+                Span::Icon(_) => None,
+            })
+            .collect()
+    }
+}
+
+/// Built-in fonts tried by [`FitText`] by default, from largest to smallest
+pub const FIT_FONTS: &[&MonoFont<'static>] = &[
+    &FONT_10X20,
+    &FONT_9X15,
+    &FONT_7X13,
+    &FONT_6X10,
+    &FONT_5X8,
+    &FONT_4X6,
+];
+
+/// Returns a style with the first (i.e. largest) of `fonts` which fits `text` into `size`
+#[must_use]
+pub fn fit_style<'a>(
+    text: &str,
+    size: Size,
+    fonts: &[&'a MonoFont<'a>],
+    color: BinaryColor,
+) -> Option<MonoTextStyle<'a, BinaryColor>> {
+    fonts
+        .iter()
+        .map(|font| MonoTextStyle::new(font, color))
+        .find(|style| {
+            advance(style, text) <= size.width && FontMetrics::of(style).height() <= size.height
+        })
+}
+
+/// A single line of text which shrinks to fit into an area
+///
+/// The largest font which fits the whole text into the area is used and the text is vertically
+/// centered. If not even the smallest font fits, the text is drawn with the smallest font and
+/// clipped.
+pub struct FitText<'a> {
+    text: &'a str,
+    area: Rectangle,
+    color: BinaryColor,
+    fonts: &'a [&'a MonoFont<'a>],
+}
+
+impl<'a> FitText<'a> {
+    /// Create a new shrink-to-fit text using the [`FIT_FONTS`]
+    #[must_use]
+    pub fn new(text: &'a str, area: Rectangle, color: BinaryColor) -> FitText<'a> {
+        FitText {
+            text,
+            area,
+            color,
+            fonts: FIT_FONTS,
+        }
+    }
+
+    /// Use a custom list of fonts, ordered from largest to smallest
+    #[must_use]
+    pub fn fonts(mut self, fonts: &'a [&'a MonoFont<'a>]) -> Self {
+        self.fonts = fonts;
+        self
+    }
+}
+
+impl Drawable for FitText<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let Some(style) =
+            fit_style(self.text, self.area.size, self.fonts, self.color).or_else(|| {
+                self.fonts
+                    .last()
+                    .map(|font| MonoTextStyle::new(font, self.color))
+            })
+        else {
+            return Ok(());
+        };
+        let baseline = FontMetrics::of(&style).baseline_centered_in(&self.area);
+        Text::with_baseline(
+            self.text,
+            Point::new(self.area.top_left.x, baseline),
+            style,
+            Baseline::Alphabetic,
+        )
+        .draw(&mut target.clipped(&self.area))?;
+        Ok(())
+    }
+}
+
+impl Describe for FitText<'_> {
+    fn describe(&self) -> String {
+        self.text.to_string()
+    }
+}
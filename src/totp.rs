@@ -0,0 +1,222 @@
+//! Time-based one-time passwords (RFC 6238)
+//!
+//! Computes the current TOTP code from a user-provided secret and shows it with a countdown bar
+//! until the next code. Secrets are never persisted by this module; use the `secrets` feature to
+//! keep them in the OS keychain.
+//!
+//! Only available with the `totp` feature.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+
+/// Generator for TOTP codes (HMAC-SHA1)
+pub struct Totp {
+    secret: Vec<u8>,
+    digits: u32,
+    period: u64,
+}
+
+impl Totp {
+    /// Create a generator from the raw secret with 6 digits and a period of 30 seconds
+    #[must_use]
+    pub fn new(secret: Vec<u8>) -> Totp {
+        Totp {
+            secret,
+            digits: 6,
+            period: 30,
+        }
+    }
+
+    /// Create a generator from a base32 encoded secret as shown by most services during setup.
+    /// Returns `None` if the secret is not valid base32.
+    #[must_use]
+    pub fn from_base32(secret: &str) -> Option<Totp> {
+        decode_base32(secret).map(Totp::new)
+    }
+
+    /// Set the number of digits (defaults to 6)
+    #[must_use]
+    pub fn digits(mut self, digits: u32) -> Self {
+        self.digits = digits.clamp(1, 9);
+        self
+    }
+
+    /// Set the period in seconds after which a new code is generated (defaults to 30)
+    #[must_use]
+    pub fn period(mut self, period: u64) -> Self {
+        self.period = period.max(1);
+        self
+    }
+
+    /// The code for the given unix timestamp in seconds
+    #[must_use]
+    #[allow(clippy::missing_panics_doc, reason = "HMAC accepts keys of any length")]
+    pub fn code_at(&self, unix_secs: u64) -> String {
+        let counter = unix_secs / self.period;
+        let mut mac =
+            Hmac::<Sha1>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(&counter.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+        // dynamic truncation as described in RFC 4226
+        let offset = usize::from(hash[hash.len() - 1] & 0x0f);
+        let value = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        format!(
+            "{:0width$}",
+            value % 10u32.pow(self.digits),
+            width = self.digits as usize
+        )
+    }
+
+    /// The current code
+    #[must_use]
+    pub fn code(&self) -> String {
+        self.code_at(unix_now())
+    }
+
+    /// Time until the current code expires
+    #[must_use]
+    pub fn remaining(&self) -> Duration {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let period = Duration::from_secs(self.period);
+        period.saturating_sub(Duration::from_nanos(
+            u64::try_from(now.as_nanos() % period.as_nanos()).unwrap_or_default(),
+        ))
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+// Helper which decodes RFC 4648 base32, ignoring case, whitespace and padding
+fn decode_base32(input: &str) -> Option<Vec<u8>> {
+    let mut bytes = vec![];
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+    for c in input.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = match c.to_ascii_uppercase() {
+            c @ 'A'..='Z' => u32::from(c) - u32::from('A'),
+            c @ '2'..='7' => u32::from(c) - u32::from('2') + 26,
+            _ => return None,
+        };
+        buffer = (buffer << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push(u8::try_from((buffer >> bits) & 0xff).ok()?);
+        }
+    }
+    Some(bytes)
+}
+
+/// Shows the current code of a [`Totp`] with a label and a bar counting down to the next code
+pub struct TotpWidget<'a> {
+    totp: &'a Totp,
+    label: &'a str,
+    area: Rectangle,
+    style: MonoTextStyle<'a, BinaryColor>,
+}
+
+impl<'a> TotpWidget<'a> {
+    /// Create a new widget filling `area`
+    #[must_use]
+    pub fn new(
+        totp: &'a Totp,
+        label: &'a str,
+        area: Rectangle,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> TotpWidget<'a> {
+        TotpWidget {
+            totp,
+            label,
+            area,
+            style,
+        }
+    }
+}
+
+impl Drawable for TotpWidget<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top_left = self.area.top_left;
+        Text::with_baseline(self.label, top_left, self.style, Baseline::Top).draw(target)?;
+        let centered = TextStyleBuilder::new()
+            .alignment(Alignment::Center)
+            .baseline(Baseline::Middle)
+            .build();
+        Text::with_text_style(&self.totp.code(), self.area.center(), self.style, centered)
+            .draw(target)?;
+
+        // the bar shrinks from the full width to nothing until the code expires
+        let period = u128::from(self.totp.period) * 1000;
+        let remaining = self.totp.remaining().as_millis();
+        let width = u32::try_from(u128::from(self.area.size.width) * remaining / period)
+            .unwrap_or(self.area.size.width);
+        let bar_top = top_left.y + self.area.size.height.cast_signed() - 2;
+        Rectangle::new(Point::new(top_left.x, bar_top), Size::new(width, 2))
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+            .draw(target)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // test vectors of RFC 6238 appendix B (SHA-1)
+    const SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn rfc6238_vectors() {
+        let totp = Totp::new(SECRET.to_vec()).digits(8);
+        for (time, code) in [
+            (59, "94287082"),
+            (1_111_111_109, "07081804"),
+            (1_111_111_111, "14050471"),
+            (1_234_567_890, "89005924"),
+            (2_000_000_000, "69279037"),
+            (20_000_000_000, "65353130"),
+        ] {
+            assert_eq!(totp.code_at(time), code, "T = {time}");
+        }
+    }
+
+    #[test]
+    fn six_digits_are_the_last_digits() {
+        let totp = Totp::new(SECRET.to_vec());
+        assert_eq!(totp.code_at(59), "287082");
+        assert_eq!(totp.code_at(1_111_111_109), "081804");
+    }
+
+    #[test]
+    fn base32_secret() {
+        let totp = Totp::from_base32("gezd gnbv gy3t qojq gezd gnbv gy3t qojq").unwrap();
+        assert_eq!(totp.secret, SECRET);
+        assert!(Totp::from_base32("not base32!").is_none());
+    }
+}
@@ -0,0 +1,204 @@
+//! CJK text rendering with GNU Unifont bitmap fonts
+//!
+//! Japanese and Chinese characters don't fit into the small mono fonts of embedded-graphics. This
+//! module loads glyphs from a font in the GNU Unifont `.hex` format (ideally a subset containing only
+//! the characters you need) and renders them with proper double-width layout: full-width glyphs
+//! advance by 16 pixels, half-width glyphs by 8 pixels.
+//!
+//! Only available with the `cjk-font` feature.
+
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind},
+    path::Path,
+};
+
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{
+        Baseline,
+        renderer::{TextMetrics, TextRenderer},
+    },
+};
+
+// every Unifont glyph is 16 pixels high with the baseline in row 13
+const GLYPH_HEIGHT: u32 = 16;
+const LAST_ROW: i32 = 15;
+const BASELINE: i32 = 13;
+const HALF_WIDTH: i32 = 8;
+const FULL_WIDTH: i32 = 16;
+
+struct Glyph {
+    wide: bool,
+    rows: [u16; 16],
+}
+
+impl Glyph {
+    fn width(&self) -> i32 {
+        if self.wide { FULL_WIDTH } else { HALF_WIDTH }
+    }
+}
+
+/// A bitmap font loaded from a GNU Unifont `.hex` file
+pub struct Unifont {
+    glyphs: HashMap<char, Glyph>,
+}
+
+impl Unifont {
+    /// Load a font from a `.hex` file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or contains invalid glyph definitions.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Unifont, Error> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse a font from the contents of a `.hex` file
+    ///
+    /// Every line has the form `CODEPOINT:BITMAP`, where the bitmap consists of 32 (half-width) or
+    /// 64 (full-width) hex digits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind `InvalidData` naming the first line which could not be parsed.
+    pub fn parse(source: &str) -> Result<Unifont, Error> {
+        let mut glyphs = HashMap::new();
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (c, glyph) = parse_line(line).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Invalid glyph definition in line {}", number + 1),
+                )
+            })?;
+            glyphs.insert(c, glyph);
+        }
+        Ok(Unifont { glyphs })
+    }
+
+    /// Whether the font contains a glyph for the given character
+    #[must_use]
+    pub fn contains(&self, c: char) -> bool {
+        self.glyphs.contains_key(&c)
+    }
+
+    // characters without a glyph are rendered as blank half-width cells
+    fn advance(&self, c: char) -> i32 {
+        self.glyphs.get(&c).map_or(HALF_WIDTH, Glyph::width)
+    }
+}
+
+// Helper which parses a single `CODEPOINT:BITMAP` line
+fn parse_line(line: &str) -> Option<(char, Glyph)> {
+    let (code, bitmap) = line.split_once(':')?;
+    let c = char::from_u32(u32::from_str_radix(code, 16).ok()?)?;
+    let digits_per_row = match bitmap.len() {
+        32 => 2,
+        64 => 4,
+        _ => return None,
+    };
+    let mut rows = [0; 16];
+    for (row, digits) in rows
+        .iter_mut()
+        .zip(bitmap.as_bytes().chunks(digits_per_row))
+    {
+        *row = u16::from_str_radix(std::str::from_utf8(digits).ok()?, 16).ok()?;
+    }
+    Some((
+        c,
+        Glyph {
+            wide: digits_per_row == 4,
+            rows,
+        },
+    ))
+}
+
+/// Text style for a [`Unifont`], usable with `embedded_graphics::text::Text`
+#[derive(Clone, Copy)]
+pub struct UnifontTextStyle<'a> {
+    font: &'a Unifont,
+    color: BinaryColor,
+}
+
+impl<'a> UnifontTextStyle<'a> {
+    /// Create a new text style
+    #[must_use]
+    pub fn new(font: &'a Unifont, color: BinaryColor) -> UnifontTextStyle<'a> {
+        UnifontTextStyle { font, color }
+    }
+
+    // y-coordinate of the top glyph row for the given baseline
+    fn top(position: Point, baseline: Baseline) -> i32 {
+        match baseline {
+            Baseline::Top => position.y,
+            Baseline::Bottom => position.y - LAST_ROW,
+            Baseline::Middle => position.y - LAST_ROW / 2,
+            Baseline::Alphabetic => position.y - BASELINE,
+        }
+    }
+}
+
+impl TextRenderer for UnifontTextStyle<'_> {
+    type Color = BinaryColor;
+
+    fn draw_string<D>(
+        &self,
+        text: &str,
+        position: Point,
+        baseline: Baseline,
+        target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let top = Self::top(position, baseline);
+        let color = self.color;
+        let mut x = position.x;
+        for c in text.chars() {
+            let Some(glyph) = self.font.glyphs.get(&c) else {
+                x += HALF_WIDTH;
+                continue;
+            };
+            let width = glyph.width();
+            target.draw_iter((0..).zip(glyph.rows).flat_map(|(row, bits)| {
+                (0..width)
+                    .filter(move |col| (bits >> (width - 1 - col)) & 1 == 1)
+                    .map(move |col| Pixel(Point::new(x + col, top + row), color))
+            }))?;
+            x += width;
+        }
+        Ok(Point::new(x, position.y))
+    }
+
+    fn draw_whitespace<D>(
+        &self,
+        width: u32,
+        position: Point,
+        _baseline: Baseline,
+        _target: &mut D,
+    ) -> Result<Point, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        Ok(position + Size::new(width, 0))
+    }
+
+    fn measure_string(&self, text: &str, position: Point, baseline: Baseline) -> TextMetrics {
+        let width: i32 = text.chars().map(|c| self.font.advance(c)).sum();
+        let top_left = Point::new(position.x, Self::top(position, baseline));
+        TextMetrics {
+            bounding_box: Rectangle::new(top_left, Size::new(width.unsigned_abs(), GLYPH_HEIGHT)),
+            next_position: position + Point::new(width, 0),
+        }
+    }
+
+    fn line_height(&self) -> u32 {
+        GLYPH_HEIGHT
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::{Duration, SystemTime};
+
+/// Detects that the system was suspended, based on gaps in the wall-clock time
+///
+/// After the OS sleeps, the Engine may have been restarted with a new address and the heartbeat
+/// has long expired. Call [`WakeDetector::check`] regularly from the update loop; when it reports
+/// a wake-up, call `GameSenseAPI::reconnect()` to re-register and redraw.
+pub struct WakeDetector {
+    last: SystemTime,
+    threshold: Duration,
+}
+
+impl WakeDetector {
+    /// Create a detector reporting gaps longer than `threshold` between two checks. The threshold
+    /// must be well above the interval in which `check` is called.
+    #[must_use]
+    pub fn new(threshold: Duration) -> WakeDetector {
+        WakeDetector {
+            last: SystemTime::now(),
+            threshold,
+        }
+    }
+
+    /// Returns true if more than the threshold passed since the last check, which usually means
+    /// the system has just woken up.
+    pub fn check(&mut self) -> bool {
+        let now = SystemTime::now();
+        // a clock set backwards is not a wake-up
+        let gap = now.duration_since(self.last).unwrap_or_default();
+        self.last = now;
+        gap > self.threshold
+    }
+}
+
+impl Default for WakeDetector {
+    /// Reports gaps longer than 30 seconds
+    fn default() -> Self {
+        WakeDetector::new(Duration::from_secs(30))
+    }
+}
@@ -0,0 +1,28 @@
+//! Ready-made widgets
+//!
+//! All widgets implement `embedded_graphics::Drawable` and can be drawn onto any display of this
+//! crate (or any other `DrawTarget` with `BinaryColor`). Widgets showing information also implement
+//! [`Describe`] to get a text description of their content.
+
+mod describe;
+mod guides;
+mod heat_map;
+mod odometer;
+mod reading;
+mod ring;
+mod stats;
+mod steps;
+mod text_file;
+mod transfers;
+
+pub use describe::{Describe, describe};
+pub use guides::Guides;
+pub use heat_map::HeatMap;
+pub(crate) use heat_map::dither;
+pub use odometer::Odometer;
+pub use reading::{ErrorPolicy, Reading, StaleStyle};
+pub use ring::Ring;
+pub use stats::StatsView;
+pub use steps::{StepShape, Steps};
+pub use text_file::{TextFile, TextFileView};
+pub use transfers::{ProgressDirectory, TransferList, Transfers};
@@ -0,0 +1,23 @@
+/// Text description of what a widget is showing
+///
+/// Useful for logging, remote inspection or screen-reader friendly companion UIs. Descriptions
+/// contain the information of the widget (labels, values, progress), not its appearance.
+pub trait Describe {
+    /// Describe the content of the widget, multiple lines are separated by `\n`
+    fn describe(&self) -> String;
+}
+
+/// Describe a whole screen: the descriptions of all widgets on it, one per line
+///
+/// ```ignore
+/// println!("{}", describe(&[&title, &progress, &transfers]));
+/// ```
+#[must_use]
+pub fn describe(widgets: &[&dyn Describe]) -> String {
+    widgets
+        .iter()
+        .map(|widget| widget.describe())
+        .filter(|description| !description.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
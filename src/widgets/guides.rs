@@ -0,0 +1,109 @@
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::Rectangle};
+
+/// Design-time overlay which draws layout guides over a rendered frame
+///
+/// Draw it last, right before `update_displays()` (or the capture of a frame), to check the
+/// placement of widgets:
+/// * a dot at every intersection of the grid (8 pixels by default)
+/// * dashed safe margins along the edges of the area
+/// * dotted outlines of widget bounding boxes
+///
+/// ```ignore
+/// let text = Text::new("Hello", Point::new(2, 8), style);
+/// text.draw(display)?;
+/// Guides::new(display.bounding_box()).margin(2).bounds_of(&text).draw(display)?;
+/// ```
+pub struct Guides {
+    area: Rectangle,
+    spacing: u32,
+    margin: u32,
+    bounds: Vec<Rectangle>,
+}
+
+impl Guides {
+    /// Create an overlay covering `area` with an 8 pixel grid and no margins
+    #[must_use]
+    pub fn new(area: Rectangle) -> Guides {
+        Guides {
+            area,
+            spacing: 8,
+            margin: 0,
+            bounds: vec![],
+        }
+    }
+
+    /// Set the grid spacing in pixels, 0 disables the grid (defaults to 8)
+    #[must_use]
+    pub fn grid(mut self, spacing: u32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Show safe margins `margin` pixels inside the edges of the area, 0 disables them
+    #[must_use]
+    pub fn margin(mut self, margin: u32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Outline a bounding box, e.g. the area of a widget
+    #[must_use]
+    pub fn bounds(mut self, bounds: Rectangle) -> Self {
+        self.bounds.push(bounds);
+        self
+    }
+
+    /// Outline the bounding box of anything with dimensions, e.g. a `Text` or a primitive
+    #[must_use]
+    pub fn bounds_of<D: Dimensions>(self, item: &D) -> Self {
+        self.bounds(item.bounding_box())
+    }
+}
+
+// the pixels along the outline of a rectangle, edge by edge
+fn outline(rect: Rectangle) -> impl Iterator<Item = Point> {
+    let corners = rect.bottom_right().map(|corner| (rect.top_left, corner));
+    corners.into_iter().flat_map(|(top_left, bottom_right)| {
+        let (left, top, right, bottom) = (top_left.x, top_left.y, bottom_right.x, bottom_right.y);
+        (left..=right)
+            .map(move |x| Point::new(x, top))
+            .chain((top..=bottom).map(move |y| Point::new(right, y)))
+            .chain((left..=right).rev().map(move |x| Point::new(x, bottom)))
+            .chain((top..=bottom).rev().map(move |y| Point::new(left, y)))
+    })
+}
+
+impl Drawable for Guides {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let mut target = target.clipped(&self.area);
+        let on = |point| Pixel(point, BinaryColor::On);
+
+        if self.spacing > 0 {
+            let dots = self.area.points().filter(|point| {
+                let offset = *point - self.area.top_left;
+                offset.x.unsigned_abs().is_multiple_of(self.spacing)
+                    && offset.y.unsigned_abs().is_multiple_of(self.spacing)
+            });
+            target.draw_iter(dots.map(on))?;
+        }
+        if self.margin > 0 {
+            // dashes of two pixels
+            let safe = self.area.offset(-self.margin.cast_signed());
+            let dashes = outline(safe)
+                .enumerate()
+                .filter(|(index, _)| index % 3 != 2)
+                .map(|(_, point)| point);
+            target.draw_iter(dashes.map(on))?;
+        }
+        for bounds in &self.bounds {
+            target.draw_iter(outline(*bounds).step_by(2).map(on))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use super::Describe;
+
+// 4x4 Bayer matrix used as thresholds for ordered dithering
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// A 2D matrix of intensities rendered as halftone densities
+///
+/// The values are given row by row. Each value is mapped to a cell of the configured size which is
+/// filled with an ordered dither pattern: the higher the value, the more pixels are lit.
+pub struct HeatMap<'a> {
+    values: &'a [f32],
+    columns: u32,
+    top_left: Point,
+    cell_size: Size,
+    range: (f32, f32),
+    labels: Option<(&'a str, &'a str, MonoTextStyle<'a, BinaryColor>)>,
+}
+
+impl<'a> HeatMap<'a> {
+    /// Create a new heat map
+    /// # Arguments
+    ///
+    /// * `values` - Intensities, row by row
+    /// * `columns` - Number of values per row
+    /// * `top_left` - Top-left corner of the matrix
+    ///
+    #[must_use]
+    pub fn new(values: &'a [f32], columns: u32, top_left: Point) -> HeatMap<'a> {
+        HeatMap {
+            values,
+            columns: columns.max(1),
+            top_left,
+            cell_size: Size::new(4, 4),
+            range: (0.0, 1.0),
+            labels: None,
+        }
+    }
+
+    /// Set the size of a single cell (defaults to 4x4 pixels)
+    #[must_use]
+    pub fn cell_size(mut self, cell_size: Size) -> Self {
+        self.cell_size = cell_size;
+        self
+    }
+
+    /// Set the values which map to an empty and a completely lit cell (defaults to 0.0 and 1.0)
+    #[must_use]
+    pub fn range(mut self, min: f32, max: f32) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    /// Add axis labels. The x label is drawn below the matrix, the y label right-aligned left of it.
+    #[must_use]
+    pub fn labels(
+        mut self,
+        x_label: &'a str,
+        y_label: &'a str,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> Self {
+        self.labels = Some((x_label, y_label, style));
+        self
+    }
+
+    fn rows(&self) -> u32 {
+        u32::try_from(self.values.len()).unwrap_or(u32::MAX) / self.columns
+    }
+
+    /// The area covered by the matrix itself, without labels
+    #[must_use]
+    pub fn matrix_area(&self) -> Rectangle {
+        Rectangle::new(
+            self.top_left,
+            Size::new(
+                self.columns * self.cell_size.width,
+                self.rows() * self.cell_size.height,
+            ),
+        )
+    }
+
+    // intensity of a value in the range 0.0..=1.0
+    fn normalize(&self, value: f32) -> f32 {
+        let (min, max) = self.range;
+        if max <= min {
+            return 0.0;
+        }
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    }
+
+    fn is_lit(&self, x: u32, y: u32) -> bool {
+        let column = x / self.cell_size.width.max(1);
+        let row = y / self.cell_size.height.max(1);
+        let value = self
+            .values
+            .get((row * self.columns + column) as usize)
+            .map_or(0.0, |value| self.normalize(*value));
+        dither(value, x, y)
+    }
+}
+
+// Helper which decides whether the pixel at (x, y) is lit in an ordered dither of `value` (0.0..=1.0)
+pub(crate) fn dither(value: f32, x: u32, y: u32) -> bool {
+    value * 16.0 > f32::from(BAYER[(y % 4) as usize][(x % 4) as usize]) + 0.5
+}
+
+impl Drawable for HeatMap<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let area = self.matrix_area();
+        let colors = (0..area.size.height)
+            .flat_map(|y| (0..area.size.width).map(move |x| BinaryColor::from(self.is_lit(x, y))));
+        target.fill_contiguous(&area, colors)?;
+
+        if let Some((x_label, y_label, style)) = self.labels {
+            let x_position = Point::new(
+                area.center().x,
+                area.top_left.y + area.size.height.cast_signed() + 1,
+            );
+            let x_style = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Top)
+                .build();
+            Text::with_text_style(x_label, x_position, style, x_style).draw(target)?;
+
+            let y_position = Point::new(area.top_left.x - 2, area.center().y);
+            let y_style = TextStyleBuilder::new()
+                .alignment(Alignment::Right)
+                .baseline(Baseline::Middle)
+                .build();
+            Text::with_text_style(y_label, y_position, style, y_style).draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for HeatMap<'_> {
+    fn describe(&self) -> String {
+        if self.values.is_empty() {
+            return "Heat map without values".to_string();
+        }
+        let (min, max) = self
+            .values
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), value| {
+                (min.min(*value), max.max(*value))
+            });
+        format!(
+            "Heat map of {} values from {min} to {max}",
+            self.values.len()
+        )
+    }
+}
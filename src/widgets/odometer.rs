@@ -0,0 +1,143 @@
+use std::time::{Duration, Instant};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use super::Describe;
+
+/// A number which animates towards new values instead of jumping to them
+///
+/// Call [`Odometer::set`] whenever the value changes and redraw periodically; every draw shows the
+/// interpolated value for the current point in time until the animation has finished.
+pub struct Odometer<'a> {
+    from: i64,
+    to: i64,
+    started: Instant,
+    duration: Duration,
+    position: Point,
+    style: MonoTextStyle<'a, BinaryColor>,
+    alignment: Alignment,
+    time: Option<Instant>,
+}
+
+impl<'a> Odometer<'a> {
+    /// Create a new odometer showing `value`
+    /// # Arguments
+    ///
+    /// * `value` - The initial value
+    /// * `position` - Position of the text, using an alphabetic baseline
+    /// * `style` - Style of the digits
+    ///
+    #[must_use]
+    pub fn new(value: i64, position: Point, style: MonoTextStyle<'a, BinaryColor>) -> Odometer<'a> {
+        Odometer {
+            from: value,
+            to: value,
+            started: Instant::now(),
+            duration: Duration::from_millis(500),
+            position,
+            style,
+            alignment: Alignment::Left,
+            time: None,
+        }
+    }
+
+    /// Set the duration of the transition between two values (defaults to 500ms)
+    #[must_use]
+    pub fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set the horizontal alignment of the number relative to its position. Right alignment keeps
+    /// the last digit in place while the number grows.
+    #[must_use]
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    /// Drive the animation from a [`Clock`](crate::Clock) instead of the wall clock: call this
+    /// with `clock.instant()` on every tick. Afterwards `set` and drawing use this time.
+    pub fn set_time(&mut self, now: Instant) {
+        self.time = Some(now);
+    }
+
+    fn now(&self) -> Instant {
+        self.time.unwrap_or_else(Instant::now)
+    }
+
+    /// Animate towards a new value, starting now
+    pub fn set(&mut self, value: i64) {
+        self.set_at(value, self.now());
+    }
+
+    /// Animate towards a new value, starting at the given point in time
+    pub fn set_at(&mut self, value: i64, now: Instant) {
+        self.from = self.value_at(now);
+        self.to = value;
+        self.started = now;
+    }
+
+    /// The value the animation is heading towards
+    #[must_use]
+    pub fn target(&self) -> i64 {
+        self.to
+    }
+
+    /// Whether the animation is still running
+    #[must_use]
+    pub fn is_animating(&self) -> bool {
+        self.from != self.to && self.now().saturating_duration_since(self.started) < self.duration
+    }
+
+    /// The value to display at the given point in time
+    #[must_use]
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    pub fn value_at(&self, now: Instant) -> i64 {
+        let elapsed = now.saturating_duration_since(self.started);
+        if elapsed >= self.duration || self.duration.is_zero() {
+            return self.to;
+        }
+        // ease-out, so that the digits slow down when approaching the new value
+        let t = elapsed.as_secs_f64() / self.duration.as_secs_f64();
+        let eased = 1.0 - (1.0 - t).powi(3);
+        // the distance between two i64 values can exceed the range of i64
+        let distance = i128::from(self.to) - i128::from(self.from);
+        let value = i128::from(self.from) + (distance as f64 * eased).round() as i128;
+        i64::try_from(value).unwrap_or(self.to)
+    }
+}
+
+impl Drawable for Odometer<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let text_style = TextStyleBuilder::new()
+            .alignment(self.alignment)
+            .baseline(Baseline::Alphabetic)
+            .build();
+        Text::with_text_style(
+            &self.value_at(self.now()).to_string(),
+            self.position,
+            self.style,
+            text_style,
+        )
+        .draw(target)?;
+        Ok(())
+    }
+}
+
+impl Describe for Odometer<'_> {
+    fn describe(&self) -> String {
+        self.to.to_string()
+    }
+}
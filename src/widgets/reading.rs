@@ -0,0 +1,173 @@
+use std::time::{Duration, Instant};
+
+use embedded_graphics::{
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Circle, Line, PointsIter, PrimitiveStyle, Rectangle},
+};
+
+/// What to show while the data source of a widget is failing
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ErrorPolicy {
+    /// Keep showing the last value, marked as stale
+    LastValue,
+    /// Replace the widget with an error icon
+    ErrorIcon,
+    /// Leave the area of the widget empty
+    Hide,
+}
+
+/// How a value which is older than expected is marked
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StaleStyle {
+    /// A small hollow dot in the top-right corner
+    Dot,
+    /// A dimming pattern over the whole widget
+    Dim,
+}
+
+/// The latest value of a data source together with its health
+///
+/// Feed every poll result into [`Reading::update`] and draw through [`Reading::draw`], which
+/// applies the configured [`ErrorPolicy`] so failures are never shown as silently frozen values.
+/// With an expected update interval, values which are older than that are marked as stale, too.
+pub struct Reading<T> {
+    value: Option<T>,
+    failing: bool,
+    policy: ErrorPolicy,
+    updated: Option<Instant>,
+    expected: Option<(Duration, StaleStyle)>,
+}
+
+impl<T> Reading<T> {
+    /// Create an empty reading
+    #[must_use]
+    pub fn new(policy: ErrorPolicy) -> Reading<T> {
+        Reading {
+            value: None,
+            failing: false,
+            policy,
+            updated: None,
+            expected: None,
+        }
+    }
+
+    /// Mark the value as stale if it was not updated within `interval`
+    #[must_use]
+    pub fn expected_interval(mut self, interval: Duration, style: StaleStyle) -> Self {
+        self.expected = Some((interval, style));
+        self
+    }
+
+    /// Record the result of polling the data source
+    pub fn update<E>(&mut self, result: Result<T, E>) {
+        match result {
+            Ok(value) => {
+                self.value = Some(value);
+                self.failing = false;
+                self.updated = Some(Instant::now());
+            }
+            Err(_) => self.failing = true,
+        }
+    }
+
+    /// The last successfully polled value
+    #[must_use]
+    pub fn value(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    /// Whether the last poll failed
+    #[must_use]
+    pub fn is_failing(&self) -> bool {
+        self.failing
+    }
+
+    /// Whether the value is older than the expected interval
+    #[must_use]
+    pub fn is_stale(&self) -> bool {
+        match (self.expected, self.updated) {
+            (Some((interval, _)), Some(updated)) => updated.elapsed() > interval,
+            _ => false,
+        }
+    }
+
+    /// Draw the reading into `area`
+    ///
+    /// `render` draws the actual widget for a value. Depending on the state of the data source and
+    /// the policy, it is called with the current or the last value, or replaced by an error icon
+    /// or an empty area.
+    ///
+    /// # Errors
+    ///
+    /// Returns the errors of the draw target or of `render`.
+    pub fn draw<D, F>(&self, area: Rectangle, target: &mut D, render: F) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = BinaryColor>,
+        F: FnOnce(&T, &mut D) -> Result<(), D::Error>,
+    {
+        if !self.failing {
+            let Some(value) = &self.value else {
+                return Ok(());
+            };
+            render(value, target)?;
+            return match self.expected {
+                Some((_, StaleStyle::Dim)) if self.is_stale() => draw_dim_pattern(area, target),
+                Some((_, StaleStyle::Dot)) if self.is_stale() => draw_stale_marker(area, target),
+                _ => Ok(()),
+            };
+        }
+        match (self.policy, &self.value) {
+            (ErrorPolicy::LastValue, Some(value)) => {
+                render(value, target)?;
+                draw_stale_marker(area, target)
+            }
+            (ErrorPolicy::LastValue | ErrorPolicy::ErrorIcon, _) => {
+                target.fill_solid(&area, BinaryColor::Off)?;
+                draw_error_icon(area.center(), target)
+            }
+            (ErrorPolicy::Hide, _) => target.fill_solid(&area, BinaryColor::Off),
+        }
+    }
+}
+
+// small hollow dot in the top-right corner of the area
+fn draw_stale_marker<D>(area: Rectangle, target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    let top_right = area.top_left + Point::new(area.size.width.cast_signed() - 5, 0);
+    Circle::new(top_right, 5)
+        .into_styled(PrimitiveStyle::with_fill(BinaryColor::Off))
+        .draw(target)?;
+    Circle::new(top_right, 5)
+        .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+        .draw(target)
+}
+
+// switch off every other pixel in a checkerboard pattern
+fn draw_dim_pattern<D>(area: Rectangle, target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    target.draw_iter(
+        area.points()
+            .filter(|point| (point.x + point.y) % 2 == 0)
+            .map(|point| Pixel(point, BinaryColor::Off)),
+    )
+}
+
+// a boxed exclamation mark
+fn draw_error_icon<D>(center: Point, target: &mut D) -> Result<(), D::Error>
+where
+    D: DrawTarget<Color = BinaryColor>,
+{
+    let stroke = PrimitiveStyle::with_stroke(BinaryColor::On, 1);
+    Rectangle::with_center(center, Size::new(7, 11))
+        .into_styled(stroke)
+        .draw(target)?;
+    Line::new(center + Point::new(0, -3), center + Point::new(0, 1))
+        .into_styled(stroke)
+        .draw(target)?;
+    Pixel(center + Point::new(0, 3), BinaryColor::On).draw(target)
+}
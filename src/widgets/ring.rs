@@ -0,0 +1,111 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Arc, Circle, PrimitiveStyle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use super::Describe;
+
+/// A circular progress indicator
+///
+/// The ring fills clockwise starting at the top. A thin track shows the remaining part and an
+/// optional label is drawn in the center.
+pub struct Ring<'a> {
+    center: Point,
+    diameter: u32,
+    progress: f32,
+    thickness: u32,
+    track: bool,
+    label: Option<(&'a str, MonoTextStyle<'a, BinaryColor>)>,
+}
+
+impl<'a> Ring<'a> {
+    /// Create a new ring
+    /// # Arguments
+    ///
+    /// * `center` - Center of the ring
+    /// * `diameter` - Outer diameter in pixels
+    /// * `progress` - Filled fraction, clamped to 0.0..=1.0
+    ///
+    #[must_use]
+    pub fn new(center: Point, diameter: u32, progress: f32) -> Ring<'a> {
+        Ring {
+            center,
+            diameter,
+            progress: progress.clamp(0.0, 1.0),
+            thickness: 3,
+            track: true,
+            label: None,
+        }
+    }
+
+    /// Set the thickness of the filled arc (defaults to 3 pixels)
+    #[must_use]
+    pub fn thickness(mut self, thickness: u32) -> Self {
+        self.thickness = thickness.max(1);
+        self
+    }
+
+    /// Whether to draw a thin circle for the unfilled part (enabled by default)
+    #[must_use]
+    pub fn track(mut self, track: bool) -> Self {
+        self.track = track;
+        self
+    }
+
+    /// Draw a label in the center of the ring
+    #[must_use]
+    pub fn label(mut self, label: &'a str, style: MonoTextStyle<'a, BinaryColor>) -> Self {
+        self.label = Some((label, style));
+        self
+    }
+}
+
+impl Drawable for Ring<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        // strokes are centered on the outline, so shrink the diameter to keep the ring inside
+        let diameter = self.diameter.saturating_sub(self.thickness);
+        if self.track {
+            Circle::with_center(self.center, diameter)
+                .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+                .draw(target)?;
+        }
+        if self.progress > 0.0 {
+            Arc::with_center(
+                self.center,
+                diameter,
+                (-90.0_f32).deg(),
+                (self.progress * 360.0).deg(),
+            )
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, self.thickness))
+            .draw(target)?;
+        }
+        if let Some((label, style)) = self.label {
+            let text_style = TextStyleBuilder::new()
+                .alignment(Alignment::Center)
+                .baseline(Baseline::Middle)
+                .build();
+            Text::with_text_style(label, self.center, style, text_style).draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for Ring<'_> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn describe(&self) -> String {
+        let percent = (self.progress * 100.0).round() as u32;
+        match self.label {
+            Some((label, _)) => format!("{label}: {percent}%"),
+            None => format!("{percent}%"),
+        }
+    }
+}
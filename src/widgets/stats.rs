@@ -0,0 +1,86 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+
+use super::Describe;
+use crate::stats::SessionStats;
+
+/// A screen listing the [`SessionStats`] of the API, one statistic per line
+///
+/// ```ignore
+/// let stats = api.stats();
+/// if let Some(display) = api.display_apex_mut() {
+///     StatsView::new(&stats, display.bounding_box(), style).draw(display)?;
+/// }
+/// ```
+pub struct StatsView<'a> {
+    stats: &'a SessionStats,
+    area: Rectangle,
+    style: MonoTextStyle<'a, BinaryColor>,
+}
+
+impl<'a> StatsView<'a> {
+    /// Create a new view filling `area`. Lines which don't fit are left out.
+    #[must_use]
+    pub fn new(
+        stats: &'a SessionStats,
+        area: Rectangle,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> StatsView<'a> {
+        StatsView { stats, area, style }
+    }
+
+    fn lines(&self) -> [String; 5] {
+        let uptime = self.stats.uptime.as_secs();
+        [
+            format!(
+                "Up {}:{:02}:{:02}",
+                uptime / 3600,
+                uptime / 60 % 60,
+                uptime % 60
+            ),
+            format!(
+                "Frames {} ({:.1}/s)",
+                self.stats.frames_sent,
+                self.stats.frame_rate()
+            ),
+            format!("Sent {} KiB", self.stats.bytes_sent / 1024),
+            format!("Reconnects {}", self.stats.reconnects),
+            format!("Dropped {}", self.stats.dropped_frames),
+        ]
+    }
+}
+
+impl Drawable for StatsView<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let line_height = self.style.font.character_size.height;
+        let visible = (self.area.size.height / line_height.max(1)) as usize;
+        let mut target = target.clipped(&self.area);
+        for (line, y) in self
+            .lines()
+            .iter()
+            .take(visible)
+            .zip((0..).step_by(line_height as usize))
+        {
+            let position = self.area.top_left + Point::new(0, y);
+            Text::with_baseline(line, position, self.style, Baseline::Top).draw(&mut target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for StatsView<'_> {
+    fn describe(&self) -> String {
+        self.lines().join("\n")
+    }
+}
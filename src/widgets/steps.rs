@@ -0,0 +1,171 @@
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{Circle, Line, PrimitiveStyle, Rectangle},
+    text::{Baseline, Text},
+};
+
+use super::Describe;
+
+/// Visual representation of the steps of a [`Steps`] widget
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum StepShape {
+    /// Circles connected by a line
+    Dots,
+    /// Adjacent bars separated by small gaps
+    Segments,
+}
+
+/// A row of discrete steps with the current step highlighted
+///
+/// Completed steps are filled, upcoming steps are outlined and the current step is marked below.
+/// An optional label (e.g. the name of the current step) is drawn below the row.
+pub struct Steps<'a> {
+    top_left: Point,
+    width: u32,
+    count: u32,
+    current: u32,
+    shape: StepShape,
+    size: u32,
+    label: Option<(&'a str, MonoTextStyle<'a, BinaryColor>)>,
+}
+
+impl<'a> Steps<'a> {
+    /// Create a new step indicator
+    /// # Arguments
+    ///
+    /// * `top_left` - Top-left corner of the row
+    /// * `width` - Width of the whole row in pixels
+    /// * `count` - Number of steps
+    /// * `current` - Index of the current step, starting at 0
+    ///
+    #[must_use]
+    pub fn new(top_left: Point, width: u32, count: u32, current: u32) -> Steps<'a> {
+        Steps {
+            top_left,
+            width,
+            count: count.max(1),
+            current,
+            shape: StepShape::Dots,
+            size: 5,
+            label: None,
+        }
+    }
+
+    /// Set the shape of the steps (defaults to [`StepShape::Dots`])
+    #[must_use]
+    pub fn shape(mut self, shape: StepShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    /// Set the diameter of dots or the height of segments (defaults to 5 pixels)
+    #[must_use]
+    pub fn size(mut self, size: u32) -> Self {
+        self.size = size.max(1);
+        self
+    }
+
+    /// Draw a label below the steps
+    #[must_use]
+    pub fn label(mut self, label: &'a str, style: MonoTextStyle<'a, BinaryColor>) -> Self {
+        self.label = Some((label, style));
+        self
+    }
+
+    fn step_style(&self, index: u32) -> PrimitiveStyle<BinaryColor> {
+        if index <= self.current {
+            PrimitiveStyle::with_fill(BinaryColor::On)
+        } else {
+            PrimitiveStyle::with_stroke(BinaryColor::On, 1)
+        }
+    }
+
+    // bounding box of the given step
+    fn step_bounds(&self, index: u32) -> Rectangle {
+        let size = self.size.cast_signed();
+        match self.shape {
+            StepShape::Dots => {
+                // distribute the dots evenly, the first and last one touching the edges
+                let free = (self.width.saturating_sub(self.size)).cast_signed();
+                let x = if self.count > 1 {
+                    free * index.cast_signed() / (self.count - 1).cast_signed()
+                } else {
+                    0
+                };
+                Rectangle::new(self.top_left + Point::new(x, 0), Size::new_equal(self.size))
+            }
+            StepShape::Segments => {
+                let gap = 2;
+                let segment = (self.width.cast_signed() - gap * (self.count.cast_signed() - 1))
+                    / self.count.cast_signed();
+                Rectangle::new(
+                    self.top_left + Point::new(index.cast_signed() * (segment + gap), 0),
+                    Size::new(segment.max(1).unsigned_abs(), size.unsigned_abs()),
+                )
+            }
+        }
+    }
+}
+
+impl Drawable for Steps<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        if self.shape == StepShape::Dots && self.count > 1 {
+            let y = self.top_left.y + (self.size / 2).cast_signed();
+            Line::new(
+                Point::new(self.top_left.x, y),
+                Point::new(self.top_left.x + self.width.cast_signed() - 1, y),
+            )
+            .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+            .draw(target)?;
+        }
+        for index in 0..self.count {
+            let bounds = self.step_bounds(index);
+            let style = self.step_style(index);
+            match self.shape {
+                StepShape::Dots => {
+                    // clear the connecting line behind upcoming (outlined) dots
+                    Circle::new(bounds.top_left, self.size)
+                        .into_styled(PrimitiveStyle::with_fill(BinaryColor::Off))
+                        .draw(target)?;
+                    Circle::new(bounds.top_left, self.size)
+                        .into_styled(style)
+                        .draw(target)?;
+                }
+                StepShape::Segments => bounds.into_styled(style).draw(target)?,
+            }
+            if index == self.current {
+                // mark the current step with a short line below it
+                let y = bounds.top_left.y + self.size.cast_signed() + 1;
+                Line::new(
+                    Point::new(bounds.top_left.x, y),
+                    Point::new(bounds.top_left.x + bounds.size.width.cast_signed() - 1, y),
+                )
+                .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+                .draw(target)?;
+            }
+        }
+        if let Some((label, style)) = self.label {
+            let position = self.top_left + Point::new(0, self.size.cast_signed() + 3);
+            Text::with_baseline(label, position, style, Baseline::Top).draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for Steps<'_> {
+    fn describe(&self) -> String {
+        let step = format!("Step {} of {}", self.current.saturating_add(1), self.count);
+        match self.label {
+            Some((label, _)) => format!("{step}: {label}"),
+            None => step,
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    io::Error,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+
+use super::Describe;
+use crate::budget::{MemoryBudget, Reservation};
+
+/// A text file which is re-read whenever it changes
+///
+/// A dead-simple integration point for shell scripts: they write to a file, the screen shows the
+/// last lines of it (see [`TextFileView`]).
+pub struct TextFile {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    lines: Vec<String>,
+    capacity: usize,
+    memory: Reservation,
+}
+
+impl TextFile {
+    /// Watch the file at `path`. The file does not need to exist yet.
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(path: P) -> TextFile {
+        TextFile {
+            path: path.as_ref().to_path_buf(),
+            modified: None,
+            lines: vec![],
+            capacity: 1000,
+            memory: Reservation::default(),
+        }
+    }
+
+    /// Set the maximum number of lines kept in memory (defaults to 1000). Only the last lines of
+    /// the file are kept, so large log files don't grow the memory usage of long-running apps.
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Share a memory budget with other components (see [`MemoryBudget`]). Lines which exceed it
+    /// are dropped like the lines beyond the capacity, starting with the first one.
+    #[must_use]
+    pub fn budget(mut self, budget: &MemoryBudget) -> Self {
+        self.memory = Reservation::new(budget);
+        let lines = std::mem::take(&mut self.lines);
+        self.lines = self.keep_within_budget(lines);
+        self
+    }
+
+    /// Bytes used by the lines kept in memory
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.memory.bytes()
+    }
+
+    /// Re-read the file if its modification time changed. Returns true if the content was
+    /// reloaded and the screen should be redrawn. A missing file is treated as empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but could not be read.
+    pub fn poll(&mut self) -> Result<bool, Error> {
+        let modified = match std::fs::metadata(&self.path) {
+            Ok(metadata) => Some(metadata.modified()?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+        if modified == self.modified && modified.is_some() {
+            return Ok(false);
+        }
+        let lines = if modified.is_some() {
+            let content = std::fs::read_to_string(&self.path)?;
+            let count = content.lines().count();
+            content
+                .lines()
+                .skip(count.saturating_sub(self.capacity))
+                .map(str::to_string)
+                .collect()
+        } else {
+            vec![]
+        };
+        self.modified = modified;
+        self.memory.shrink(self.memory.bytes());
+        let lines = self.keep_within_budget(lines);
+        let changed = lines != self.lines;
+        self.lines = lines;
+        Ok(changed)
+    }
+
+    // Helper which reserves the memory of the last lines and drops the lines before them which
+    // don't fit into the budget anymore
+    fn keep_within_budget(&mut self, mut lines: Vec<String>) -> Vec<String> {
+        let kept = lines
+            .iter()
+            .rev()
+            .take_while(|line| self.memory.grow(std::mem::size_of::<String>() + line.len()))
+            .count();
+        lines.drain(..lines.len() - kept);
+        lines
+    }
+
+    /// The lines of the file as of the last [`TextFile::poll`]
+    #[must_use]
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+/// Shows the last lines of a [`TextFile`] which fit into an area
+pub struct TextFileView<'a> {
+    file: &'a TextFile,
+    area: Rectangle,
+    style: MonoTextStyle<'a, BinaryColor>,
+}
+
+impl<'a> TextFileView<'a> {
+    /// Create a new view filling `area`
+    #[must_use]
+    pub fn new(
+        file: &'a TextFile,
+        area: Rectangle,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> TextFileView<'a> {
+        TextFileView { file, area, style }
+    }
+}
+
+impl Drawable for TextFileView<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let line_height = self.style.font.character_size.height;
+        let visible = (self.area.size.height / line_height.max(1)) as usize;
+        let lines = self.file.lines();
+        let tail = &lines[lines.len().saturating_sub(visible)..];
+        let mut target = target.clipped(&self.area);
+        for (line, y) in tail.iter().zip((0..).step_by(line_height as usize)) {
+            let position = self.area.top_left + Point::new(0, y);
+            Text::with_baseline(line, position, self.style, Baseline::Top).draw(&mut target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for TextFileView<'_> {
+    fn describe(&self) -> String {
+        let line_height = self.style.font.character_size.height;
+        let visible = (self.area.size.height / line_height.max(1)) as usize;
+        let lines = self.file.lines();
+        lines[lines.len().saturating_sub(visible)..].join("\n")
+    }
+}
@@ -0,0 +1,366 @@
+use std::{
+    collections::HashMap,
+    io::{Error, ErrorKind},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::{PrimitiveStyle, Rectangle},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+use super::Describe;
+use crate::budget::{MemoryBudget, Reservation};
+
+struct Transfer {
+    name: String,
+    done: u64,
+    total: u64,
+    started: Instant,
+}
+
+impl Transfer {
+    #[allow(clippy::cast_precision_loss)]
+    fn progress(&self) -> f32 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        (self.done as f32 / self.total as f32).clamp(0.0, 1.0)
+    }
+
+    // memory used by the transfer, as charged to the budget
+    fn bytes(&self) -> usize {
+        std::mem::size_of::<Transfer>() + self.name.len()
+    }
+
+    // estimate based on the average rate since the transfer started
+    #[allow(clippy::cast_precision_loss)]
+    fn eta(&self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f64();
+        if self.done == 0 || elapsed <= 0.0 {
+            return None;
+        }
+        let rate = self.done as f64 / elapsed;
+        let remaining = self.total.saturating_sub(self.done) as f64;
+        Some(Duration::from_secs_f64(remaining / rate))
+    }
+}
+
+/// Progress of several long-running transfers (downloads, renders, backups, ...)
+///
+/// Feed progress updates with [`Transfers::update`] and draw the list with [`TransferList`].
+pub struct Transfers {
+    items: Vec<Transfer>,
+    capacity: usize,
+    memory: Reservation,
+}
+
+impl Default for Transfers {
+    fn default() -> Self {
+        Transfers {
+            items: vec![],
+            capacity: 32,
+            memory: Reservation::default(),
+        }
+    }
+}
+
+impl Transfers {
+    /// Create an empty list of transfers
+    #[must_use]
+    pub fn new() -> Transfers {
+        Transfers::default()
+    }
+
+    /// Set the maximum number of tracked transfers (defaults to 32). When a new transfer exceeds
+    /// it, the oldest finished transfer is dropped, or the oldest transfer if none has finished.
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Share a memory budget with other components (see [`MemoryBudget`]). When a new transfer
+    /// exceeds it, transfers are dropped like when exceeding the capacity.
+    #[must_use]
+    pub fn budget(mut self, budget: &MemoryBudget) -> Self {
+        let mut memory = Reservation::new(budget);
+        self.items.retain(|item| memory.grow(item.bytes()));
+        self.memory = memory;
+        self
+    }
+
+    /// Bytes used by the tracked transfers
+    #[must_use]
+    pub fn memory_usage(&self) -> usize {
+        self.memory.bytes()
+    }
+
+    /// Report the progress of a transfer. Unknown names start a new transfer.
+    /// # Arguments
+    ///
+    /// * `name` - Name of the transfer as shown on the screen
+    /// * `done` - Amount of work done so far (e.g. bytes)
+    /// * `total` - Total amount of work
+    ///
+    pub fn update(&mut self, name: &str, done: u64, total: u64) {
+        if let Some(item) = self.items.iter_mut().find(|item| item.name == name) {
+            item.done = done;
+            item.total = total;
+        } else {
+            let item = Transfer {
+                name: name.to_string(),
+                done,
+                total,
+                started: Instant::now(),
+            };
+            while self.items.len() >= self.capacity || !self.memory.grow(item.bytes()) {
+                if !self.evict() {
+                    println!("Warning: Transfer \"{name}\" exceeds the memory budget");
+                    return;
+                }
+            }
+            self.items.push(item);
+        }
+    }
+
+    /// Remove a transfer from the list
+    pub fn remove(&mut self, name: &str) {
+        self.retain(|item| item.name != name);
+    }
+
+    /// Remove all transfers which are complete
+    pub fn remove_finished(&mut self) {
+        self.retain(|item| item.done < item.total);
+    }
+
+    // Helper which drops the oldest finished transfer, or the oldest one if none has finished.
+    // Returns false if there is nothing left to drop.
+    fn evict(&mut self) -> bool {
+        if self.items.is_empty() {
+            return false;
+        }
+        let index = self
+            .items
+            .iter()
+            .position(|item| item.done >= item.total)
+            .unwrap_or(0);
+        let item = self.items.remove(index);
+        self.memory.shrink(item.bytes());
+        true
+    }
+
+    // Helper which keeps the transfers matching `keep` and returns the memory of the others
+    fn retain(&mut self, keep: impl Fn(&Transfer) -> bool) {
+        let memory = &mut self.memory;
+        self.items.retain(|item| {
+            let kept = keep(item);
+            if !kept {
+                memory.shrink(item.bytes());
+            }
+            kept
+        });
+    }
+
+    /// Number of tracked transfers
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether no transfer is tracked
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Estimated remaining time of a transfer, `None` if unknown
+    #[must_use]
+    pub fn eta(&self, name: &str) -> Option<Duration> {
+        self.items
+            .iter()
+            .find(|item| item.name == name)
+            .and_then(|item| item.eta(Instant::now()))
+    }
+}
+
+/// Feeds [`Transfers`] from the files of a directory
+///
+/// Like [`TextFile`](super::TextFile), a dead-simple integration point for scripts and other
+/// processes: every file in the directory is one transfer, named after the file (without
+/// extension), containing the work done and the total work separated by whitespace, e.g.
+/// `1048576 5242880`. Deleting the file removes the transfer, files in another format are ignored.
+pub struct ProgressDirectory {
+    path: PathBuf,
+    seen: HashMap<String, (u64, u64)>,
+}
+
+impl ProgressDirectory {
+    /// Watch the directory at `path`. The directory does not need to exist yet.
+    #[must_use]
+    pub fn new<P: AsRef<Path>>(path: P) -> ProgressDirectory {
+        ProgressDirectory {
+            path: path.as_ref().to_path_buf(),
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Re-read the progress files and update `transfers`. Returns true if any transfer changed
+    /// and the screen should be redrawn. A missing directory is treated as empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory exists but could not be read.
+    pub fn poll(&mut self, transfers: &mut Transfers) -> Result<bool, Error> {
+        let mut current = HashMap::new();
+        match std::fs::read_dir(&self.path) {
+            Ok(entries) => {
+                for entry in entries {
+                    let path = entry?.path();
+                    if let (Some(name), Some(progress)) = (
+                        path.file_stem().and_then(|stem| stem.to_str()),
+                        read_progress(&path),
+                    ) {
+                        current.insert(name.to_string(), progress);
+                    }
+                }
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        if current == self.seen {
+            return Ok(false);
+        }
+        for name in self.seen.keys().filter(|name| !current.contains_key(*name)) {
+            transfers.remove(name);
+        }
+        for (name, (done, total)) in &current {
+            if self.seen.get(name) != Some(&(*done, *total)) {
+                transfers.update(name, *done, *total);
+            }
+        }
+        self.seen = current;
+        Ok(true)
+    }
+}
+
+// Helper which reads a progress file, `None` if it is no file in the expected format
+fn read_progress(path: &Path) -> Option<(u64, u64)> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let mut numbers = content.split_whitespace().map(str::parse::<u64>);
+    match (numbers.next(), numbers.next(), numbers.next()) {
+        (Some(Ok(done)), Some(Ok(total)), None) => Some((done, total)),
+        _ => None,
+    }
+}
+
+// Helper which formats a duration as `1h02`, `3m05` or `42s`
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}", secs / 3600, secs % 3600 / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// One row per transfer: name and ETA above a progress bar
+///
+/// Rows which don't fit into the area are skipped.
+pub struct TransferList<'a> {
+    transfers: &'a Transfers,
+    area: Rectangle,
+    style: MonoTextStyle<'a, BinaryColor>,
+}
+
+impl<'a> TransferList<'a> {
+    /// Create a new list filling `area`
+    #[must_use]
+    pub fn new(
+        transfers: &'a Transfers,
+        area: Rectangle,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> TransferList<'a> {
+        TransferList {
+            transfers,
+            area,
+            style,
+        }
+    }
+}
+
+impl Drawable for TransferList<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let now = Instant::now();
+        let text_height = self.style.font.character_size.height;
+        let bar_height = 3;
+        let row_height = text_height + bar_height + 2;
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        let width = self.area.size.width;
+        let rows = (self.area.size.height / row_height) as usize;
+
+        for (index, item) in self.transfers.items.iter().take(rows).enumerate() {
+            let top = self.area.top_left
+                + Point::new(
+                    0,
+                    (row_height * u32::try_from(index).unwrap_or(0)).cast_signed(),
+                );
+            Text::with_baseline(&item.name, top, self.style, Baseline::Top).draw(target)?;
+            if let Some(eta) = item.eta(now) {
+                let top_right = top + Point::new(width.cast_signed() - 1, 0);
+                Text::with_text_style(&format_eta(eta), top_right, self.style, right)
+                    .draw(target)?;
+            }
+            let bar = Rectangle::new(
+                top + Point::new(0, text_height.cast_signed() + 1),
+                Size::new(width, bar_height),
+            );
+            bar.into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 1))
+                .draw(target)?;
+            let filled = (item.progress() * width as f32) as u32;
+            Rectangle::new(bar.top_left, Size::new(filled, bar_height))
+                .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+                .draw(target)?;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for TransferList<'_> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn describe(&self) -> String {
+        let now = Instant::now();
+        self.transfers
+            .items
+            .iter()
+            .map(|item| {
+                let percent = (item.progress() * 100.0).round() as u32;
+                match item.eta(now) {
+                    Some(eta) => format!("{}: {percent}%, {}s left", item.name, eta.as_secs()),
+                    None => format!("{}: {percent}%", item.name),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
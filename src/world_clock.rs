@@ -0,0 +1,126 @@
+//! Clocks for several time zones at a glance
+//!
+//! [`WorldClock`] shows the current time of labeled time zones one below the other, e.g. for
+//! teams spread over several continents:
+//!
+//! ```ignore
+//! use chrono_tz::{America::New_York, Asia::Tokyo, Europe::Berlin};
+//!
+//! let zones = [("NYC", New_York), ("BER", Berlin), ("TYO", Tokyo)];
+//! if let Some(display) = api.display_apex_mut() {
+//!     let _ = WorldClock::new(&zones, Point::zero(), style).draw(display);
+//! }
+//! ```
+//!
+//! Only available with the `world-clock` feature.
+
+use chrono::{DateTime, Utc};
+pub use chrono_tz::Tz;
+use embedded_graphics::{
+    mono_font::MonoTextStyle,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Baseline, Text},
+};
+
+use crate::widgets::Describe;
+
+/// Shows the time of several labeled time zones, one per line
+///
+/// Zones which are already on the next day (or still on the previous one) compared to the first
+/// zone are marked with `+1` (or `-1`).
+pub struct WorldClock<'a> {
+    zones: &'a [(&'a str, Tz)],
+    top_left: Point,
+    style: MonoTextStyle<'a, BinaryColor>,
+    now: Option<DateTime<Utc>>,
+    seconds: bool,
+}
+
+impl<'a> WorldClock<'a> {
+    /// Create a clock for the given zones, the first line starting at `top_left`
+    #[must_use]
+    pub fn new(
+        zones: &'a [(&'a str, Tz)],
+        top_left: Point,
+        style: MonoTextStyle<'a, BinaryColor>,
+    ) -> WorldClock<'a> {
+        WorldClock {
+            zones,
+            top_left,
+            style,
+            now: None,
+            seconds: false,
+        }
+    }
+
+    /// Show the given point in time instead of the current time, e.g. for tests
+    #[must_use]
+    pub fn at(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Show the seconds as well (defaults to hours and minutes only)
+    #[must_use]
+    pub fn seconds(mut self, seconds: bool) -> Self {
+        self.seconds = seconds;
+        self
+    }
+
+    // Helper which formats the lines of all zones
+    fn lines(&self) -> Vec<String> {
+        let now = self.now.unwrap_or_else(Utc::now);
+        let format = if self.seconds { "%H:%M:%S" } else { "%H:%M" };
+        let width = self
+            .zones
+            .iter()
+            .map(|(label, _)| label.chars().count())
+            .max()
+            .unwrap_or(0);
+        let reference = self
+            .zones
+            .first()
+            .map(|(_, zone)| now.with_timezone(zone).date_naive());
+        self.zones
+            .iter()
+            .map(|(label, zone)| {
+                let time = now.with_timezone(zone);
+                let day = match reference.map(|reference| time.date_naive().cmp(&reference)) {
+                    Some(std::cmp::Ordering::Greater) => " +1",
+                    Some(std::cmp::Ordering::Less) => " -1",
+                    _ => "",
+                };
+                format!("{label:<width$} {}{day}", time.format(format))
+            })
+            .collect()
+    }
+}
+
+impl Drawable for WorldClock<'_> {
+    type Color = BinaryColor;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let line_height = self.style.font.character_size.height.cast_signed();
+        let mut position = self.top_left;
+        for line in self.lines() {
+            Text::with_baseline(&line, position, self.style, Baseline::Top).draw(target)?;
+            position.y += line_height;
+        }
+        Ok(())
+    }
+}
+
+impl Describe for WorldClock<'_> {
+    fn describe(&self) -> String {
+        self.lines()
+            .iter()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}